@@ -1,13 +1,23 @@
 mod context;
+mod diagnostic;
 mod error;
+mod fetch_policy;
 mod interface;
+mod lockfile;
+mod overlay;
 mod r#type;
+mod validation;
 
 pub mod oas30;
 pub mod oas31;
 pub mod swagger2;
 
 pub use context::*;
+pub use diagnostic::*;
 pub use error::*;
+pub use fetch_policy::*;
 pub use interface::*;
+pub use lockfile::*;
+pub use overlay::*;
 pub use r#type::*;
+pub use validation::*;
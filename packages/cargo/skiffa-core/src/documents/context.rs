@@ -1,12 +1,25 @@
 use super::interface::DocumentFactory;
 use super::{DocumentInterface, DocumentType};
-use crate::documents::DocumentConfiguration;
 use crate::documents::{oas30, oas31, swagger2};
+use crate::documents::{
+  Diagnostic, DiagnosticContainer, DiagnosticSeverity, DocumentConfiguration, FetchPolicy,
+  Lockfile, LockfileMode, Overlay,
+};
 use crate::error::Error;
+use crate::lint::{well_known_rules, Rule, RuleContext};
 use crate::models;
-use crate::utils::{NodeCache, NodeLocation};
-use std::cell::RefCell;
-use std::collections::BTreeMap;
+use crate::transform::{
+  bundle_document, convert_swagger2_document, dereference_document, upgrade_oas30_document,
+  OutputFormat,
+};
+use crate::utils::{
+  find_node_references, DefaultFetcher, FetchTextError, Fetcher, HttpCacheFetcher, JsFetcher,
+  JsonPath, MemoryFetcher, NodeCache, NodeCacheError, NodeLocation, NodePosition,
+  NodePositionContainer,
+};
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::rc;
 use wasm_bindgen::prelude::*;
 
@@ -18,8 +31,75 @@ pub struct DocumentContext {
    */
   factories: RefCell<BTreeMap<DocumentType, DocumentFactory>>,
   documents: RefCell<BTreeMap<NodeLocation, Box<dyn DocumentInterface>>>,
+  /**
+   * diagnostics reported by documents, models may be created more than once
+   * so a set is used to prevent reporting the same diagnostic twice
+   */
+  diagnostics: RefCell<BTreeSet<Diagnostic>>,
+  operation_name_strategy: Cell<models::OperationNameStrategy>,
+  /**
+   * in strict mode no models are made from documents that are not valid
+   * according to the meta-schema of the document
+   */
+  strict: Cell<bool>,
+  invalid_documents: RefCell<BTreeSet<NodeLocation>>,
+  rules: RefCell<Vec<Box<dyn Rule>>>,
+  /**
+   * configured severity of rules by rule name, None if the rule is disabled
+   */
+  rule_severities: RefCell<BTreeMap<String, Option<DiagnosticSeverity>>>,
+  /**
+   * maximum number of documents that are retrieved at the same time, the
+   * default is used if not set
+   */
+  parallelism: Cell<Option<usize>>,
+  progress_handler: RefCell<Option<ProgressHandler>>,
+  /**
+   * fetchers by the prefix of the locations they retrieve
+   */
+  fetchers: RefCell<BTreeMap<String, rc::Rc<dyn Fetcher>>>,
+  /**
+   * if there is a http cache, http(s) documents are retrieved via the cache
+   * instead of the default fetcher
+   */
+  http_cache: RefCell<Option<rc::Rc<HttpCacheFetcher>>>,
+  /**
+   * if there is a lockfile, retrieved documents are verified against it, or
+   * pinned in it, depending on the mode
+   */
+  lockfile: RefCell<Option<Lockfile>>,
+  lockfile_mode: Cell<LockfileMode>,
+  fetch_policy: RefCell<FetchPolicy>,
+  /**
+   * locations of the overlays that are applied to a document, by the root
+   * location of the document
+   */
+  overlays: RefCell<BTreeMap<NodeLocation, Vec<NodeLocation>>>,
+  /**
+   * locations of the overlays that are applied to the node in the cache, by
+   * the root location of the node
+   */
+  applied_overlays: RefCell<BTreeMap<NodeLocation, Vec<NodeLocation>>>,
+}
+
+/// Number of documents that are retrieved at the same time if no parallelism
+/// is configured.
+///
+pub const DEFAULT_PARALLELISM: usize = 8;
+
+/// Progress of loading documents, reported every time a document is loaded
+/// (or failed to load). The number of discovered documents grows while
+/// references to new documents are found.
+///
+#[derive(Debug, Clone)]
+pub struct LoadProgress {
+  pub location: NodeLocation,
+  pub loaded_count: usize,
+  pub discovered_count: usize,
 }
 
+pub type ProgressHandler = Box<dyn Fn(&LoadProgress)>;
+
 impl DocumentContext {
   pub fn new() -> Self {
     Self::default()
@@ -36,6 +116,675 @@ impl DocumentContext {
   pub fn get_node(&self, retrieval_location: &NodeLocation) -> Option<serde_json::Value> {
     self.cache.borrow().get_node(retrieval_location).cloned()
   }
+
+  pub fn query(&self, retrieval_location: &NodeLocation, path: &JsonPath) -> Vec<NodeLocation> {
+    self.cache.borrow().query(retrieval_location, path)
+  }
+
+  pub fn get_node_position(&self, retrieval_location: &NodeLocation) -> Option<NodePosition> {
+    self.cache.borrow().get_node_position(retrieval_location)
+  }
+
+  pub fn report_diagnostic(&self, diagnostic: Diagnostic) {
+    self.diagnostics.borrow_mut().insert(diagnostic);
+  }
+
+  pub fn report_error(&self, location: &NodeLocation, message: &str, cause: impl Into<Error>) {
+    let cause = cause.into();
+    self.report_diagnostic(Diagnostic {
+      location: location.clone(),
+      message: format!("{} ({})", message, cause),
+      severity: DiagnosticSeverity::Error,
+      cause: Some(cause),
+    });
+  }
+
+  /// Reports the error of a failed result as a diagnostic so the caller can
+  /// skip the failed item and continue with the next one instead of aborting.
+  ///
+  pub fn report_result<T, E>(
+    &self,
+    location: &NodeLocation,
+    message: &str,
+    result: Result<T, E>,
+  ) -> Option<T>
+  where
+    E: Into<Error>,
+  {
+    match result {
+      Ok(value) => Some(value),
+      Err(error) => {
+        self.report_error(location, message, error);
+        None
+      }
+    }
+  }
+
+  pub fn get_diagnostics(&self) -> Vec<Diagnostic> {
+    self.diagnostics.borrow().iter().cloned().collect()
+  }
+
+  pub fn set_strict(&self, strict: bool) {
+    self.strict.set(strict);
+  }
+
+  pub fn set_operation_name_strategy(&self, strategy: models::OperationNameStrategy) {
+    self.operation_name_strategy.set(strategy);
+  }
+
+  /// Sets the maximum number of documents that are retrieved at the same
+  /// time, at least one document is always retrieved.
+  ///
+  pub fn set_parallelism(&self, parallelism: usize) {
+    self.parallelism.set(Some(parallelism.max(1)));
+  }
+
+  /// Retrieve documents with a location (fetch string) that starts with the
+  /// prefix via the fetcher. If more than one prefix matches a location, the
+  /// longest prefix wins. Locations that match no prefix are retrieved via the
+  /// default fetcher (http(s) or file system), or the http cache if there is
+  /// one.
+  ///
+  pub fn register_fetcher(&self, prefix: &str, fetcher: rc::Rc<dyn Fetcher>) {
+    self
+      .fetchers
+      .borrow_mut()
+      .insert(prefix.to_owned(), fetcher);
+  }
+
+  pub fn get_fetcher(&self, location: &str) -> rc::Rc<dyn Fetcher> {
+    self
+      .fetchers
+      .borrow()
+      .iter()
+      .filter(|(prefix, _fetcher)| location.starts_with(prefix.as_str()))
+      .max_by_key(|(prefix, _fetcher)| prefix.len())
+      .map(|(_prefix, fetcher)| fetcher.clone())
+      .or_else(|| {
+        let http_cache = self.http_cache.borrow().clone()?;
+        let location_lower = location.to_lowercase();
+        (location_lower.starts_with("http://") || location_lower.starts_with("https://"))
+          .then_some(http_cache as rc::Rc<dyn Fetcher>)
+      })
+      .unwrap_or_else(|| rc::Rc::new(DefaultFetcher))
+  }
+
+  /// Keep a copy of every http(s) document in the cache, or stop using a
+  /// cache if there is none. Fetchers that are registered for a location take
+  /// precedence over the cache.
+  ///
+  pub fn set_http_cache(&self, http_cache: Option<HttpCacheFetcher>) {
+    *self.http_cache.borrow_mut() = http_cache.map(rc::Rc::new);
+  }
+
+  /**
+  true if a fetcher is registered for the location, otherwise the default
+  fetcher is used
+  */
+  fn has_fetcher(&self, location: &str) -> bool {
+    self
+      .fetchers
+      .borrow()
+      .keys()
+      .any(|prefix| location.starts_with(prefix.as_str()))
+  }
+
+  /**
+  locations with a custom scheme (like in memory documents) are not files or
+  hosts, they can only be retrieved by a fetcher that is registered for them.
+  The default fetcher would read them as a file.
+  */
+  fn check_location(&self, fetch_policy: &FetchPolicy, fetch_location: &str) -> Result<(), String> {
+    if FetchPolicy::has_custom_scheme(fetch_location) && self.has_fetcher(fetch_location) {
+      return Ok(());
+    }
+
+    fetch_policy.check_location(fetch_location)
+  }
+
+  pub fn set_fetch_policy(&self, fetch_policy: FetchPolicy) {
+    *self.fetch_policy.borrow_mut() = fetch_policy;
+  }
+
+  pub fn get_fetch_policy(&self) -> FetchPolicy {
+    self.fetch_policy.borrow().clone()
+  }
+
+  /**
+  report a violation of the fetch policy and return the error that prevents
+  the document from being retrieved
+  */
+  fn check_fetch_policy(
+    &self,
+    retrieval_location: &NodeLocation,
+    result: Result<(), String>,
+  ) -> Result<(), Error> {
+    result.map_err(|reason| {
+      self.report_error(
+        retrieval_location,
+        &format!("fetch policy violation, {}", reason),
+        Error::FetchPolicyViolation,
+      );
+      Error::FetchPolicyViolation
+    })
+  }
+
+  /**
+  check a (root) location that is reached by following references against the
+  complete fetch policy. The depth is the number of references that were
+  followed to get to the location, the count is the number of documents
+  including this one.
+  */
+  pub(crate) fn check_retrieval(
+    &self,
+    fetch_policy: &FetchPolicy,
+    retrieval_location: &NodeLocation,
+    depth: usize,
+    count: usize,
+  ) -> Result<(), Error> {
+    let fetch_location = retrieval_location.to_fetch_string();
+    let result = self
+      .check_location(fetch_policy, &fetch_location)
+      .and_then(|()| fetch_policy.check_depth(depth))
+      .and_then(|()| fetch_policy.check_document_count(count));
+    self.check_fetch_policy(retrieval_location, result)
+  }
+
+  /// Loads the root node at the location into the cache, without making a
+  /// document of it. This is for nodes that are not documents themselves,
+  /// like schemas in separate files. The node is retrieved according to the
+  /// fetch policy and the lockfile, like documents.
+  ///
+  pub async fn load_node(&self, retrieval_location: &NodeLocation) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.set_root();
+
+    self.load_overlays(&retrieval_location).await?;
+    if self.get_node(&retrieval_location).is_none() {
+      let data = self.fetch_document(&retrieval_location).await?;
+      self.load_text(&retrieval_location, &data)?;
+    }
+
+    self.apply_overlays(&retrieval_location)
+  }
+
+  /**
+  retrieve the text of the document at the (root) location according to the
+  fetch policy
+  */
+  async fn fetch_document(&self, retrieval_location: &NodeLocation) -> Result<String, Error> {
+    let fetch_location = retrieval_location.to_fetch_string();
+    let fetch_policy = self.get_fetch_policy();
+    self.check_fetch_policy(
+      retrieval_location,
+      self.check_location(&fetch_policy, &fetch_location),
+    )?;
+
+    let fetcher = self.get_fetcher(&fetch_location);
+    fetcher
+      .fetch_text(&fetch_location, fetch_policy.maximum_document_size)
+      .await
+      .map_err(|error| self.map_fetch_error(retrieval_location, &fetch_policy, error))
+  }
+
+  /**
+  a document that is larger than the maximum size is a violation of the fetch
+  policy, the fetcher stopped reading it
+  */
+  fn map_fetch_error(
+    &self,
+    retrieval_location: &NodeLocation,
+    fetch_policy: &FetchPolicy,
+    error: FetchTextError,
+  ) -> Error {
+    match (error, fetch_policy.maximum_document_size) {
+      (FetchTextError::TooLarge, Some(maximum_document_size)) => self
+        .check_fetch_policy(
+          retrieval_location,
+          Err(format!(
+            "document size exceeds the maximum of {}",
+            maximum_document_size
+          )),
+        )
+        .unwrap_err(),
+      (error, _) => NodeCacheError::from(error).into(),
+    }
+  }
+
+  /**
+  load the text of a retrieved document into the cache, after it is verified
+  against (or pinned in) the lockfile
+  */
+  fn load_text(&self, retrieval_location: &NodeLocation, data: &str) -> Result<(), Error> {
+    self.pin_document(retrieval_location, data)?;
+    self
+      .cache
+      .borrow_mut()
+      .load_from_text(retrieval_location, data)?;
+
+    Ok(())
+  }
+
+  /// Configures the overlays that are applied (in order) to the document at
+  /// the location when it is loaded, before a document is made of it. If the
+  /// node of the document is already in the cache the overlays are applied
+  /// the next time it is loaded. A document that is already made is not made
+  /// again, this is reported as a warning.
+  ///
+  pub fn set_overlays(
+    &self,
+    retrieval_location: &NodeLocation,
+    overlay_locations: Vec<NodeLocation>,
+  ) {
+    let retrieval_location = retrieval_location.set_root();
+    let overlay_locations: Vec<_> = overlay_locations
+      .iter()
+      .map(|overlay_location| overlay_location.set_root())
+      .collect();
+
+    let applied = self
+      .applied_overlays
+      .borrow()
+      .get(&retrieval_location)
+      .cloned()
+      .unwrap_or_default();
+    if self.documents.borrow().contains_key(&retrieval_location) && applied != overlay_locations {
+      self.report_diagnostic(Diagnostic {
+        location: retrieval_location.clone(),
+        message: "the document is already loaded, the overlays are not applied".to_owned(),
+        severity: DiagnosticSeverity::Warning,
+        cause: None,
+      });
+    }
+
+    self
+      .overlays
+      .borrow_mut()
+      .insert(retrieval_location, overlay_locations);
+  }
+
+  pub fn get_overlays(&self, retrieval_location: &NodeLocation) -> Vec<NodeLocation> {
+    self
+      .overlays
+      .borrow()
+      .get(&retrieval_location.set_root())
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  /**
+  load the overlays of the document at the location into the cache, so they
+  can be applied when the document is loaded. Overlays are retrieved like any
+  other document, so they are pinned in the lockfile too.
+  */
+  async fn load_overlays(&self, retrieval_location: &NodeLocation) -> Result<(), Error> {
+    for overlay_location in self.get_overlays(retrieval_location) {
+      if self.get_node(&overlay_location).is_some() {
+        continue;
+      }
+
+      let data = self.fetch_document(&overlay_location).await?;
+      self.pin_document(&overlay_location, &data)?;
+      self
+        .cache
+        .borrow_mut()
+        .load_from_text(&overlay_location, &data)?;
+    }
+
+    Ok(())
+  }
+
+  /**
+  apply the actions of the (loaded) overlays of the document at the (root)
+  location to the node of the document in the cache. Overlays that are already
+  applied to the node are not applied again. The positions of the nodes that
+  are changed by an overlay are dropped, they get the position of the node
+  the overlay action targets.
+  */
+  fn apply_overlays(&self, retrieval_location: &NodeLocation) -> Result<(), Error> {
+    let overlay_locations = self.get_overlays(retrieval_location);
+    let applied_overlay_locations = self
+      .applied_overlays
+      .borrow()
+      .get(retrieval_location)
+      .cloned()
+      .unwrap_or_default();
+
+    /*
+    the overlays of a node cannot be undone, so overlays can only be added
+    after the node is loaded
+    */
+    let Some(pending_overlay_locations) =
+      overlay_locations.strip_prefix(applied_overlay_locations.as_slice())
+    else {
+      self.report_error(
+        retrieval_location,
+        "could not apply overlays, other overlays are already applied to the document",
+        Error::Conflict,
+      );
+      return Err(Error::Conflict);
+    };
+    if pending_overlay_locations.is_empty() {
+      return Ok(());
+    }
+    self
+      .applied_overlays
+      .borrow_mut()
+      .insert(retrieval_location.clone(), overlay_locations.clone());
+
+    let mut first_error = None;
+
+    for overlay_location in pending_overlay_locations {
+      let overlay = self
+        .get_node(overlay_location)
+        .ok_or(Error::NotFound)
+        .and_then(|node| Overlay::from_node(&node));
+      let overlay = match overlay {
+        Ok(overlay) => overlay,
+        Err(error) => {
+          self.report_error(overlay_location, "could not load overlay", error);
+          first_error.get_or_insert(error);
+          continue;
+        }
+      };
+
+      for (index, action) in overlay.actions.iter().enumerate() {
+        let action_location =
+          overlay_location.push_pointer(vec!["actions".to_owned(), index.to_string()]);
+        let mut cache = self.cache.borrow_mut();
+        let result = cache
+          .get_node_mut(retrieval_location)
+          .ok_or(Error::NotFound)
+          .and_then(|node| action.apply(node));
+
+        match result {
+          Ok(changed_pointers) if changed_pointers.is_empty() => {
+            self.report_diagnostic(Diagnostic {
+              location: action_location,
+              message: "overlay action target selects no nodes".to_owned(),
+              severity: DiagnosticSeverity::Warning,
+              cause: None,
+            })
+          }
+          Ok(changed_pointers) => {
+            for changed_pointer in changed_pointers {
+              cache.drop_node_positions(&retrieval_location.push_pointer(changed_pointer));
+            }
+          }
+          Err(error) => {
+            self.report_error(&action_location, "could not apply overlay action", error);
+            first_error.get_or_insert(error);
+          }
+        }
+      }
+    }
+
+    match first_error {
+      Some(error) => Err(error),
+      None => Ok(()),
+    }
+  }
+
+  /// Sets the lockfile that retrieved documents are verified against, or
+  /// pinned in. In update mode the pins are built from the documents that are
+  /// retrieved from now on, so pins of documents that are no longer
+  /// referenced are dropped.
+  ///
+  pub fn set_lockfile(&self, mut lockfile: Option<Lockfile>, mode: LockfileMode) {
+    if mode == LockfileMode::Update {
+      if let Some(lockfile) = &mut lockfile {
+        lockfile.documents.clear();
+      }
+    }
+    *self.lockfile.borrow_mut() = lockfile;
+    self.lockfile_mode.set(mode);
+  }
+
+  pub fn get_lockfile(&self) -> Option<Lockfile> {
+    self.lockfile.borrow().clone()
+  }
+
+  /**
+  verify the retrieved document against its pin in the lockfile, or (in update
+  mode) pin it. Does nothing if there is no lockfile.
+  */
+  fn pin_document(&self, retrieval_location: &NodeLocation, data: &str) -> Result<(), Error> {
+    let mut lockfile = self.lockfile.borrow_mut();
+    let Some(lockfile) = lockfile.as_mut() else {
+      return Ok(());
+    };
+
+    let location = retrieval_location.to_fetch_string();
+    let content_hash = Lockfile::make_content_hash(data);
+    match self.lockfile_mode.get() {
+      LockfileMode::Update => {
+        lockfile.documents.insert(location, content_hash);
+      }
+      LockfileMode::Verify => match lockfile.documents.get(&location) {
+        Some(pinned_content_hash) if *pinned_content_hash == content_hash => {}
+        Some(pinned_content_hash) => {
+          self.report_error(
+            retrieval_location,
+            &format!(
+              "content hash {} does not match {} in the lockfile",
+              content_hash, pinned_content_hash
+            ),
+            Error::LockfileMismatch,
+          );
+          return Err(Error::LockfileMismatch);
+        }
+        None => {
+          self.report_error(
+            retrieval_location,
+            "document is not pinned in the lockfile",
+            Error::LockfileMismatch,
+          );
+          return Err(Error::LockfileMismatch);
+        }
+      },
+    }
+
+    Ok(())
+  }
+
+  pub fn set_progress_handler(&self, handler: Option<ProgressHandler>) {
+    *self.progress_handler.borrow_mut() = handler;
+  }
+
+  fn report_progress(&self, progress: &LoadProgress) {
+    if let Some(handler) = self.progress_handler.borrow().as_ref() {
+      handler(progress);
+    }
+  }
+
+  pub fn register_rule(&self, rule: Box<dyn Rule>) {
+    self.rules.borrow_mut().push(rule);
+  }
+
+  /// Configures the severity of the violations of a rule, a severity of None
+  /// disables the rule.
+  ///
+  pub fn set_rule_severity(&self, rule_name: &str, severity: Option<DiagnosticSeverity>) {
+    self
+      .rule_severities
+      .borrow_mut()
+      .insert(rule_name.to_owned(), severity);
+  }
+
+  pub fn get_document_locations(&self) -> Vec<NodeLocation> {
+    self.documents.borrow().keys().cloned().collect()
+  }
+
+  /// Locations of the schemas that reference themselves, directly or via
+  /// other schemas. Generators can not emit these as plain nested types, they
+  /// need lazy or boxed types to break the recursion.
+  ///
+  pub fn get_recursive_schema_locations(&self) -> BTreeSet<NodeLocation> {
+    let documents = self.documents.borrow();
+    documents
+      .values()
+      .flat_map(|document| document.get_schema_locations().into_iter().flatten())
+      .filter(|schema_location| self.is_recursive_schema(schema_location))
+      .collect()
+  }
+
+  /**
+  follow every reference from the schema (and from the nodes it references)
+  and see if we end up at the schema, or one of its ancestors, again
+  */
+  fn is_recursive_schema(&self, schema_location: &NodeLocation) -> bool {
+    let mut queue = vec![schema_location.clone()];
+    let mut visited_locations = BTreeSet::new();
+
+    while let Some(location) = queue.pop() {
+      if !visited_locations.insert(location.clone()) {
+        continue;
+      }
+      let Some(node) = self.get_node(&location) else {
+        continue;
+      };
+
+      for reference in find_node_references(&location, &node) {
+        if reference.target_location.contains(schema_location) {
+          return true;
+        }
+        queue.push(reference.target_location);
+      }
+    }
+
+    false
+  }
+
+  /// Makes the api model of the (loaded) document at the location. Errors
+  /// that prevent the model from being made are also reported as diagnostics.
+  ///
+  pub fn get_api_model(
+    &self,
+    retrieval_location: &NodeLocation,
+  ) -> Result<rc::Rc<models::Api>, Error> {
+    let documents = self.documents.borrow();
+    let document = documents.get(retrieval_location).ok_or(Error::NotFound)?;
+
+    if self.strict.get() && self.invalid_documents.borrow().contains(retrieval_location) {
+      self.report_error(
+        retrieval_location,
+        "will not make the api model of an invalid document",
+        Error::ValidationFailed,
+      );
+      return Err(Error::ValidationFailed);
+    }
+    let mut api_model = document.get_api_model().map_err(|error| {
+      self.report_error(retrieval_location, "could not make the api model", error);
+      Error::from(error)
+    })?;
+    self.name_operations(rc::Rc::make_mut(&mut api_model));
+
+    Ok(api_model)
+  }
+
+  /// Checks the document at the location with every enabled rule, violations
+  /// are reported as diagnostics.
+  ///
+  pub fn lint(&self, retrieval_location: &NodeLocation) -> Result<(), Error> {
+    let api_model = self.get_api_model(retrieval_location)?;
+    let rule_context = RuleContext::new(retrieval_location, &api_model, self);
+
+    let rules = self.rules.borrow();
+    for rule in rules.iter() {
+      let severity = match self.rule_severities.borrow().get(rule.name()) {
+        Some(severity) => *severity,
+        None => Some(rule.default_severity()),
+      };
+      let Some(severity) = severity else {
+        continue;
+      };
+
+      for violation in rule.check(&rule_context) {
+        self.report_diagnostic(Diagnostic {
+          location: violation.location,
+          message: format!("{}: {}", rule.name(), violation.message),
+          severity,
+          cause: None,
+        });
+      }
+    }
+
+    Ok(())
+  }
+
+  /**
+  give operations without an operationId a name based on the method and the
+  path pattern (or webhook name). Synthesized names never take a name that is
+  used as an operationId, if a synthesized name is already taken a number is
+  appended. Duplicate operationId's are reported.
+  */
+  fn name_operations(&self, api: &mut models::Api) {
+    let strategy = self.operation_name_strategy.get();
+
+    let operations = api
+      .paths
+      .iter_mut()
+      .flat_map(|path| {
+        let path = rc::Rc::make_mut(path);
+        let pattern = path.pattern.clone();
+        path
+          .operations
+          .iter_mut()
+          .map(move |operation| (pattern.clone(), operation))
+      })
+      .chain(api.webhooks.iter_mut().flat_map(|webhook| {
+        let webhook = rc::Rc::make_mut(webhook);
+        let name = webhook.name.clone();
+        webhook
+          .operations
+          .iter_mut()
+          .map(move |operation| (name.clone(), operation))
+      }));
+
+    let mut operation_ids = BTreeSet::new();
+    let mut unnamed_operations = Vec::new();
+    for (pattern, operation) in operations {
+      match &operation.operation_id {
+        Some(operation_id) => {
+          if !operation_ids.insert(operation_id.clone()) {
+            self.report_diagnostic(Diagnostic {
+              location: operation.location.clone(),
+              message: format!("operationId {} is not unique", operation_id),
+              severity: DiagnosticSeverity::Warning,
+              cause: None,
+            });
+          }
+        }
+        None => unnamed_operations.push((pattern, operation)),
+      }
+    }
+
+    let mut names = operation_ids;
+    for (pattern, operation) in unnamed_operations {
+      let operation = rc::Rc::make_mut(operation);
+      let base_name = strategy.make_operation_name(operation.method, &pattern);
+      let mut name = base_name.clone();
+      let mut suffix = 2;
+      while names.contains(&name) {
+        name = format!("{}{}", base_name, suffix);
+        suffix += 1;
+      }
+
+      if name != base_name {
+        self.report_diagnostic(Diagnostic {
+          location: operation.location.clone(),
+          message: format!(
+            "synthesized operation name {} is already taken, using {}",
+            base_name, name
+          ),
+          severity: DiagnosticSeverity::Information,
+          cause: None,
+        });
+      }
+
+      names.insert(name.clone());
+      operation.name = name;
+    }
+  }
 }
 
 #[wasm_bindgen]
@@ -83,103 +832,573 @@ impl DocumentContextContainer {
     );
   }
 
-  #[wasm_bindgen(js_name = "loadFromLocation")]
-  #[allow(clippy::await_holding_refcell_ref)]
-  pub async fn load_from_location(&self, retrieval_location: &str) -> Result<(), Error> {
-    let retrieval_location = retrieval_location.parse().unwrap();
-    let mut queue = Vec::new();
-    queue.push(retrieval_location);
+  /// In strict mode, no api model is made for documents that are not valid
+  /// according to their meta-schema.
+  ///
+  #[wasm_bindgen(js_name = "setStrict")]
+  pub fn set_strict(&self, strict: bool) {
+    self.0.set_strict(strict);
+  }
 
-    while let Some(retrieval_location) = queue.pop() {
-      if self.0.documents.borrow().contains_key(&retrieval_location) {
-        continue;
-      }
+  #[wasm_bindgen(js_name = "setOperationNameStrategy")]
+  pub fn set_operation_name_strategy(&self, strategy: models::OperationNameStrategy) {
+    self.0.set_operation_name_strategy(strategy);
+  }
 
-      self
-        .0
-        .cache
-        .borrow_mut()
-        .load_from_location(&retrieval_location)
-        .await?;
+  /// Retrieve documents with a location that starts with the prefix by
+  /// calling the callback with the location. The callback should return the
+  /// text of the document, or a promise of the text.
+  ///
+  #[wasm_bindgen(js_name = "registerFetchCallback")]
+  pub fn register_fetch_callback(&self, prefix: &str, callback: js_sys::Function) {
+    self
+      .0
+      .register_fetcher(prefix, rc::Rc::new(JsFetcher::new(callback)));
+  }
 
-      let document_type = self
-        .0
-        .cache
-        .borrow()
-        .get_node(&retrieval_location)
-        .ok_or(Error::NotFound)?
-        .try_into()?;
-
-      let document = {
-        let factories = self.0.factories.borrow();
-        let factory = factories.get(&document_type).ok_or(Error::NotFound)?;
-        factory(DocumentConfiguration {
-          retrieval_location: retrieval_location.clone(),
-        })
-      };
+  /// Use the text as the document at the location, instead of retrieving it.
+  ///
+  #[wasm_bindgen(js_name = "registerMemoryDocument")]
+  pub fn register_memory_document(&self, location: &str, data: String) -> Result<(), Error> {
+    let location: NodeLocation = location.parse()?;
+    let location = location.to_fetch_string();
 
-      for referenced_location in document.get_referenced_locations()? {
-        let referenced_retrieval_location =
-          retrieval_location.join(&referenced_location).set_root();
+    let mut fetcher = MemoryFetcher::new();
+    fetcher.insert(location.clone(), data);
+    self.0.register_fetcher(&location, rc::Rc::new(fetcher));
 
-        queue.push(referenced_retrieval_location);
-      }
+    Ok(())
+  }
 
-      assert!(self
-        .0
-        .documents
-        .borrow_mut()
-        .insert(retrieval_location.clone(), document)
-        .is_none());
-    }
+  /// Keep a copy of every http(s) document in the directory and revalidate
+  /// it on the next retrieval. In offline mode http(s) documents are only
+  /// served from the cache. Without a directory no cache is used.
+  ///
+  #[wasm_bindgen(js_name = "setHttpCache")]
+  pub fn set_http_cache(&self, directory: Option<String>, offline: bool) {
+    let http_cache = directory.map(|directory| {
+      let mut http_cache = HttpCacheFetcher::new(directory);
+      http_cache.set_offline(offline);
+      http_cache
+    });
+    self.0.set_http_cache(http_cache);
+  }
+
+  /// Verify retrieved documents against the lockfile (json), or pin them in
+  /// the lockfile in update mode. Without data an empty lockfile is used, in
+  /// update mode the lockfile only keeps the pins of the documents that are
+  /// retrieved. The (updated) lockfile is available via getLockfile.
+  ///
+  #[wasm_bindgen(js_name = "setLockfile")]
+  pub fn set_lockfile(&self, data: Option<String>, mode: LockfileMode) -> Result<(), Error> {
+    let lockfile = match data {
+      Some(data) => Lockfile::from_json(&data)?,
+      None => Lockfile::new(),
+    };
+    self.0.set_lockfile(Some(lockfile), mode);
 
     Ok(())
   }
 
-  #[wasm_bindgen(js_name = "getApiModel")]
-  pub fn get_api_model(&self, retrieval_location: &str) -> Option<models::ApiContainer> {
-    let retrieval_location = retrieval_location.parse().unwrap();
-    let documents = self.0.documents.borrow();
-    let document = documents.get(&retrieval_location)?;
-    let api_model = document.get_api_model().unwrap();
+  #[wasm_bindgen(js_name = "getLockfile")]
+  pub fn get_lockfile(&self) -> Result<Option<String>, Error> {
+    self
+      .0
+      .get_lockfile()
+      .map(|lockfile| lockfile.to_json())
+      .transpose()
+  }
 
-    Some(api_model.into())
+  /// Only retrieve http(s) documents from these hosts, or their subdomains.
+  /// Without hosts, documents from every host are retrieved.
+  ///
+  #[wasm_bindgen(js_name = "setAllowedHosts")]
+  pub fn set_allowed_hosts(&self, hosts: Option<Vec<String>>) {
+    let mut fetch_policy = self.0.get_fetch_policy();
+    fetch_policy.allowed_hosts = hosts;
+    self.0.set_fetch_policy(fetch_policy);
   }
 
-  #[wasm_bindgen(js_name = "getSchemas")]
-  pub fn get_schemas(&self) -> Vec<DocumentSchemaContainer> {
-    let documents = self.0.documents.borrow();
-    documents
-      .values()
-      .flat_map(|document| {
-        document
-          .get_schema_locations()
-          .into_iter()
-          .flatten()
-          .map(|schema_location| {
-            rc::Rc::new(DocumentSchema {
-              schema_location,
-              document_location: document.get_document_location(),
-              default_schema_id: document.get_default_schema_id(),
-            })
-            .into()
-          })
-      })
-      .collect()
+  /// Never retrieve http(s) documents from these hosts, or their subdomains.
+  ///
+  #[wasm_bindgen(js_name = "setDeniedHosts")]
+  pub fn set_denied_hosts(&self, hosts: Vec<String>) {
+    let mut fetch_policy = self.0.get_fetch_policy();
+    fetch_policy.denied_hosts = hosts;
+    self.0.set_fetch_policy(fetch_policy);
   }
-}
 
-pub struct DocumentSchema {
-  pub schema_location: NodeLocation,
-  pub document_location: NodeLocation,
-  pub default_schema_id: String,
-}
+  /// Only retrieve file documents from this directory, or its descendants.
+  ///
+  #[wasm_bindgen(js_name = "setFileRoot")]
+  pub fn set_file_root(&self, file_root: Option<String>) {
+    let mut fetch_policy = self.0.get_fetch_policy();
+    fetch_policy.file_root = file_root;
+    self.0.set_fetch_policy(fetch_policy);
+  }
 
-#[wasm_bindgen]
-#[derive(Clone)]
-pub struct DocumentSchemaContainer(rc::Rc<DocumentSchema>);
+  #[wasm_bindgen(js_name = "setMaximumDocumentSize")]
+  pub fn set_maximum_document_size(&self, maximum_document_size: Option<usize>) {
+    let mut fetch_policy = self.0.get_fetch_policy();
+    fetch_policy.maximum_document_size = maximum_document_size;
+    self.0.set_fetch_policy(fetch_policy);
+  }
 
-#[wasm_bindgen]
+  #[wasm_bindgen(js_name = "setMaximumDocumentCount")]
+  pub fn set_maximum_document_count(&self, maximum_document_count: Option<usize>) {
+    let mut fetch_policy = self.0.get_fetch_policy();
+    fetch_policy.maximum_document_count = maximum_document_count;
+    self.0.set_fetch_policy(fetch_policy);
+  }
+
+  #[wasm_bindgen(js_name = "setMaximumDepth")]
+  pub fn set_maximum_depth(&self, maximum_depth: Option<usize>) {
+    let mut fetch_policy = self.0.get_fetch_policy();
+    fetch_policy.maximum_depth = maximum_depth;
+    self.0.set_fetch_policy(fetch_policy);
+  }
+
+  #[wasm_bindgen(js_name = "setParallelism")]
+  pub fn set_parallelism(&self, parallelism: usize) {
+    self.0.set_parallelism(parallelism);
+  }
+
+  /// The handler is called with the location of the document, the number of
+  /// loaded documents and the number of discovered documents every time a
+  /// document is loaded.
+  ///
+  #[wasm_bindgen(js_name = "setProgressHandler")]
+  pub fn set_progress_handler(&self, handler: Option<js_sys::Function>) {
+    let handler = handler.map(|handler| -> ProgressHandler {
+      Box::new(move |progress| {
+        /*
+        an error thrown by the handler should not stop the loading
+        */
+        let _ = handler.call3(
+          &JsValue::NULL,
+          &progress.location.to_string().into(),
+          &progress.loaded_count.into(),
+          &progress.discovered_count.into(),
+        );
+      })
+    });
+    self.0.set_progress_handler(handler);
+  }
+
+  /// Loads the document at the location and all documents it references.
+  /// Documents are retrieved concurrently, but never more than the configured
+  /// parallelism at the same time. Documents that fail to load are reported as
+  /// diagnostics and the loading continues with the other documents. If any
+  /// document failed, the first error is returned after all documents are
+  /// processed.
+  ///
+  #[wasm_bindgen(js_name = "loadFromLocation")]
+  pub async fn load_from_location(&self, retrieval_location: &str) -> Result<(), Error> {
+    let retrieval_location: NodeLocation = retrieval_location.parse()?;
+    let parallelism = self.0.parallelism.get().unwrap_or(DEFAULT_PARALLELISM);
+
+    let fetch_policy = self.0.get_fetch_policy();
+
+    /*
+    locations with their depth, the number of references that were followed
+    to get to the location
+    */
+    let mut queue = VecDeque::from([(retrieval_location, 0)]);
+    /*
+    every root location is only retrieved once
+    */
+    let mut discovered_locations = BTreeSet::new();
+    let mut retrievals = FuturesUnordered::new();
+    let mut loaded_count = 0;
+
+    let mut retrieval_depth = 0;
+
+    let mut first_error = None;
+    loop {
+      while retrievals.len() < parallelism {
+        /*
+        locations are retrieved breadth first, so a location is reached via
+        the shortest chain of references and checked with that depth.
+        Retrievals finish in any order, so the next depth is only started
+        when every retrieval of the current depth is finished
+        */
+        let Some((_retrieval_location, depth)) = queue.front() else {
+          break;
+        };
+        if *depth > retrieval_depth && !retrievals.is_empty() {
+          break;
+        }
+        let (retrieval_location, depth) = queue.pop_front().unwrap();
+        retrieval_depth = depth;
+        if self.0.documents.borrow().contains_key(&retrieval_location)
+          || discovered_locations.contains(&retrieval_location)
+        {
+          continue;
+        }
+
+        if let Err(error) = self.0.check_retrieval(
+          &fetch_policy,
+          &retrieval_location,
+          depth,
+          discovered_locations.len() + 1,
+        ) {
+          first_error.get_or_insert(error);
+          continue;
+        }
+        discovered_locations.insert(retrieval_location.clone());
+
+        let fetch_location = retrieval_location.to_fetch_string();
+
+        /*
+        documents that are already in the cache are not retrieved again
+        */
+        let cached = self.0.get_node(&retrieval_location).is_some();
+        let fetcher = self.0.get_fetcher(&fetch_location);
+        let fetch_policy = &fetch_policy;
+        retrievals.push(async move {
+          let result = if cached {
+            Ok(None)
+          } else {
+            fetcher
+              .fetch_text(&fetch_location, fetch_policy.maximum_document_size)
+              .await
+              .map(Some)
+              .map_err(|error| {
+                self
+                  .0
+                  .map_fetch_error(&retrieval_location, fetch_policy, error)
+              })
+          };
+          (retrieval_location, depth, result)
+        });
+      }
+
+      let Some((retrieval_location, depth, result)) = retrievals.next().await else {
+        break;
+      };
+
+      /*
+      the overlays of a retrieved (or cached) document are loaded before the
+      document, so they can be applied before a document is made of it
+      */
+      let result = match result {
+        Ok(data) => self
+          .0
+          .load_overlays(&retrieval_location)
+          .await
+          .map(|()| data),
+        result => result,
+      };
+
+      let mut referenced_locations = Vec::new();
+      let result = result
+        .and_then(|data| self.load_document(&retrieval_location, data, &mut referenced_locations));
+      if let Err(error) = result {
+        self
+          .0
+          .report_error(&retrieval_location, "could not load document", error);
+        first_error.get_or_insert(error);
+      }
+      queue.extend(
+        referenced_locations
+          .into_iter()
+          .map(|referenced_location| (referenced_location, depth + 1)),
+      );
+
+      loaded_count += 1;
+      self.0.report_progress(&LoadProgress {
+        location: retrieval_location,
+        loaded_count,
+        discovered_count: discovered_locations.len(),
+      });
+    }
+
+    match first_error {
+      Some(error) => Err(error),
+      None => Ok(()),
+    }
+  }
+
+  /// Configures the overlays (by their locations) that are applied, in
+  /// order, to the document at the location before a document is made of it.
+  ///
+  #[wasm_bindgen(js_name = "setOverlays")]
+  pub fn set_overlays(
+    &self,
+    retrieval_location: &str,
+    overlay_locations: Vec<String>,
+  ) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let overlay_locations = overlay_locations
+      .iter()
+      .map(|overlay_location| overlay_location.parse())
+      .collect::<Result<_, _>>()?;
+    self.0.set_overlays(&retrieval_location, overlay_locations);
+
+    Ok(())
+  }
+
+  /// Locations of the nodes that are selected by the JSONPath (RFC 9535)
+  /// query, the root of the query is the node at the location.
+  ///
+  #[wasm_bindgen(js_name = "query")]
+  pub fn query(&self, retrieval_location: &str, path: &str) -> Result<Vec<String>, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let path = path.parse()?;
+
+    Ok(
+      self
+        .0
+        .query(&retrieval_location, &path)
+        .iter()
+        .map(|location| location.to_string())
+        .collect(),
+    )
+  }
+
+  #[wasm_bindgen(js_name = "getApiModel")]
+  pub fn get_api_model(&self, retrieval_location: &str) -> Result<models::ApiContainer, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let api_model = self.0.get_api_model(&retrieval_location)?;
+
+    Ok(api_model.into())
+  }
+
+  #[wasm_bindgen(js_name = "registerWellKnownRules")]
+  pub fn register_well_known_rules(&self) {
+    for rule in well_known_rules() {
+      self.0.register_rule(rule);
+    }
+  }
+
+  /// Configures the severity of a rule, or disables the rule if the severity
+  /// is undefined.
+  ///
+  #[wasm_bindgen(js_name = "setRuleSeverity")]
+  pub fn set_rule_severity(&self, rule_name: &str, severity: Option<DiagnosticSeverity>) {
+    self.0.set_rule_severity(rule_name, severity);
+  }
+
+  /// Lints the (loaded) document at the location, violations are available
+  /// via getDiagnostics.
+  ///
+  #[wasm_bindgen(js_name = "lint")]
+  pub fn lint(&self, retrieval_location: &str) -> Result<(), Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    self.0.lint(&retrieval_location)
+  }
+
+  /// Bundles the document at the location and every document it references
+  /// into one self-contained document.
+  ///
+  #[wasm_bindgen(js_name = "bundle")]
+  pub async fn bundle(
+    &self,
+    retrieval_location: &str,
+    format: OutputFormat,
+  ) -> Result<String, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let node = bundle_document(&self.0, &retrieval_location).await?;
+
+    format.serialize(&node)
+  }
+
+  /// Dereferences the document at the location into one document where every
+  /// reference is replaced by the node it refers to. Only references that
+  /// are part of a cycle are left, as local references.
+  ///
+  #[wasm_bindgen(js_name = "dereference")]
+  pub async fn dereference(
+    &self,
+    retrieval_location: &str,
+    format: OutputFormat,
+  ) -> Result<String, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let node = dereference_document(&self.0, &retrieval_location).await?;
+
+    format.serialize(&node)
+  }
+
+  /// Converts the swagger 2.0 document at the location into an OpenAPI 3.0
+  /// or 3.1 document. Everything that could not be converted without losing
+  /// information is reported as a diagnostic.
+  ///
+  #[wasm_bindgen(js_name = "convertSwagger2")]
+  pub async fn convert_swagger2(
+    &self,
+    retrieval_location: &str,
+    document_type: DocumentType,
+    format: OutputFormat,
+  ) -> Result<String, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let node = convert_swagger2_document(&self.0, &retrieval_location, document_type).await?;
+
+    format.serialize(&node)
+  }
+
+  /// Upgrades the OpenAPI 3.0 document at the location to an OpenAPI 3.1
+  /// document. Every change is reported as a diagnostic.
+  ///
+  #[wasm_bindgen(js_name = "upgradeOas30")]
+  pub async fn upgrade_oas30(
+    &self,
+    retrieval_location: &str,
+    format: OutputFormat,
+  ) -> Result<String, Error> {
+    let retrieval_location = retrieval_location.parse()?;
+    let node = upgrade_oas30_document(&self.0, &retrieval_location).await?;
+
+    format.serialize(&node)
+  }
+
+  #[wasm_bindgen(js_name = "getSchemas")]
+  pub fn get_schemas(&self) -> Vec<DocumentSchemaContainer> {
+    let recursive_schema_locations = self.0.get_recursive_schema_locations();
+    let documents = self.0.documents.borrow();
+    documents
+      .values()
+      .flat_map(|document| {
+        document
+          .get_schema_locations()
+          .into_iter()
+          .flatten()
+          .map(|schema_location| {
+            rc::Rc::new(DocumentSchema {
+              document_location: document.get_document_location(),
+              default_schema_id: document.get_default_schema_id(),
+              recursive: recursive_schema_locations.contains(&schema_location),
+              schema_location,
+              schema_node: None,
+            })
+            .into()
+          })
+          .chain(document.get_derived_schemas().into_iter().flatten().map(
+            |(schema_location, schema_node)| {
+              rc::Rc::new(DocumentSchema {
+                document_location: document.get_document_location(),
+                default_schema_id: document.get_default_schema_id(),
+                // derived schemas have no references
+                recursive: false,
+                schema_location,
+                schema_node: Some(schema_node),
+              })
+              .into()
+            },
+          ))
+      })
+      .collect()
+  }
+
+  /// The line and column of the node at the location in the source text, for
+  /// instance the location of a diagnostic.
+  ///
+  #[wasm_bindgen(js_name = "getNodePosition")]
+  pub fn get_node_position(&self, retrieval_location: &str) -> Option<NodePositionContainer> {
+    let retrieval_location = retrieval_location.parse().ok()?;
+    let node_position = self.0.get_node_position(&retrieval_location)?;
+
+    Some(rc::Rc::new(node_position).into())
+  }
+
+  #[wasm_bindgen(js_name = "getDiagnostics")]
+  pub fn get_diagnostics(&self) -> Vec<DiagnosticContainer> {
+    self
+      .0
+      .get_diagnostics()
+      .into_iter()
+      .map(|diagnostic| rc::Rc::new(diagnostic).into())
+      .collect()
+  }
+}
+
+impl DocumentContextContainer {
+  /**
+  load a retrieved document (or a document that is already in the cache if
+  there is no data) and queue the documents it references
+  */
+  fn load_document(
+    &self,
+    retrieval_location: &NodeLocation,
+    data: Option<String>,
+    queue: &mut Vec<NodeLocation>,
+  ) -> Result<(), Error> {
+    /*
+    another load on the same context may have made the document while this
+    load was fetching it, the referenced documents are queued by that load
+    */
+    if self.0.documents.borrow().contains_key(retrieval_location) {
+      return Ok(());
+    }
+
+    if let Some(data) = data {
+      self.0.load_text(retrieval_location, &data)?;
+    }
+    self.0.apply_overlays(retrieval_location)?;
+
+    let document_type = self
+      .0
+      .cache
+      .borrow()
+      .get_node(retrieval_location)
+      .ok_or(Error::NotFound)?
+      .try_into()?;
+
+    let document = {
+      let factories = self.0.factories.borrow();
+      let factory = factories.get(&document_type).ok_or(Error::NotFound)?;
+      factory(DocumentConfiguration {
+        retrieval_location: retrieval_location.clone(),
+      })
+    };
+
+    let validation_diagnostics = document.validate()?;
+    if !validation_diagnostics.is_empty() {
+      self
+        .0
+        .invalid_documents
+        .borrow_mut()
+        .insert(retrieval_location.clone());
+    }
+    for diagnostic in validation_diagnostics {
+      self.0.report_diagnostic(diagnostic);
+    }
+
+    for referenced_location in document.get_referenced_locations()? {
+      let referenced_retrieval_location = retrieval_location.join(&referenced_location).set_root();
+
+      queue.push(referenced_retrieval_location);
+    }
+
+    self
+      .0
+      .documents
+      .borrow_mut()
+      .insert(retrieval_location.clone(), document);
+
+    Ok(())
+  }
+}
+
+pub struct DocumentSchema {
+  pub schema_location: NodeLocation,
+  pub document_location: NodeLocation,
+  pub default_schema_id: String,
+  /**
+   * the schema references itself, directly or via other schemas
+   */
+  pub recursive: bool,
+  /**
+   * the schema if it is not the node at the schema location, but derived
+   * from it
+   */
+  pub schema_node: Option<serde_json::Value>,
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct DocumentSchemaContainer(rc::Rc<DocumentSchema>);
+
+#[wasm_bindgen]
 impl DocumentSchemaContainer {
   #[wasm_bindgen(getter, js_name = "schemaLocation")]
   pub fn schema_location(&self) -> String {
@@ -195,6 +1414,26 @@ impl DocumentSchemaContainer {
   pub fn default_schema_id(&self) -> String {
     self.0.default_schema_id.clone()
   }
+
+  #[wasm_bindgen(getter, js_name = "recursive")]
+  pub fn recursive(&self) -> bool {
+    self.0.recursive
+  }
+
+  /// The derived schema (as json) if the schema is not the node at the schema
+  /// location. A swagger 2 query parameter for instance has the keywords of
+  /// a schema, but also keywords like `in` and `name`, so a schema is derived
+  /// from it. Derived schemas should be loaded from this node and be
+  /// identified by the schema location.
+  ///
+  #[wasm_bindgen(getter, js_name = "schemaNode")]
+  pub fn schema_node(&self) -> Option<String> {
+    self
+      .0
+      .schema_node
+      .as_ref()
+      .map(|schema_node| schema_node.to_string())
+  }
 }
 
 impl From<rc::Rc<DocumentSchema>> for DocumentSchemaContainer {
@@ -216,10 +1455,18 @@ mod tests {
     let location = "../../../fixtures/specifications/echo.yaml#";
 
     context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    context.set_strict(true);
     let api = context.get_api_model(location).unwrap();
 
     assert_eq!(api.location(), location.to_string());
 
+    // no servers means a single server with url /
+    let servers = api.servers();
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0].url(), "/");
+
     for path in api.paths() {
       assert!(path.id() > 0);
       for operation in path.operations() {
@@ -255,4 +1502,1847 @@ mod tests {
 
     assert_eq!(api.location(), location.to_string());
   }
+
+  #[tokio::test]
+  async fn test_oas30_request_body_component() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/request-body-component.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    assert_eq!(api.location(), location.to_string());
+
+    let operation = &api.paths()[0].operations()[0];
+    assert!(operation.body_required());
+    assert_eq!(operation.body_description(), Some("A message".to_owned()));
+
+    let bodies = operation.bodies();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(
+      bodies[0].schema_id(),
+      Some(format!(
+        "{}/components/requestBodies/message/content/application~1json/schema",
+        location
+      ))
+    );
+
+    let schemas = context.get_schemas();
+    assert!(schemas
+      .iter()
+      .any(|schema| Some(schema.schema_location()) == bodies[0].schema_id()));
+  }
+
+  #[tokio::test]
+  async fn test_oas30_parameter_style() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/parameter-style.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    let operation = &api.paths()[0].operations()[0];
+
+    let path_parameters = operation.path_parameters();
+    assert_eq!(path_parameters[0].style(), "matrix");
+    assert!(!path_parameters[0].explode());
+    assert!(path_parameters[0].required());
+
+    let query_parameters = operation.query_parameters();
+    assert_eq!(query_parameters[0].name(), "filter");
+    assert_eq!(query_parameters[0].style(), "deepObject");
+    assert!(query_parameters[0].explode());
+
+    assert_eq!(query_parameters[1].name(), "tags");
+    assert_eq!(query_parameters[1].style(), "form");
+    assert!(!query_parameters[1].explode());
+    assert!(query_parameters[1].deprecated());
+    assert_eq!(
+      query_parameters[1].description(),
+      Some("tags to filter on".to_owned())
+    );
+
+    assert_eq!(query_parameters[2].name(), "coordinates");
+    assert_eq!(query_parameters[2].style(), "form");
+    assert!(query_parameters[2].explode());
+    assert_eq!(
+      query_parameters[2].content_type(),
+      Some("application/json".to_owned())
+    );
+    assert_eq!(
+      query_parameters[2].schema_id(),
+      Some(format!(
+        "{}/paths/~1noop~1%7Bid%7D/get/parameters/3/content/application~1json/schema",
+        location
+      ))
+    );
+
+    let header_parameters = operation.header_parameters();
+    assert_eq!(header_parameters[0].style(), "simple");
+    assert!(!header_parameters[0].explode());
+
+    let schemas = context.get_schemas();
+    assert!(schemas
+      .iter()
+      .any(|schema| Some(schema.schema_location()) == query_parameters[2].schema_id()));
+  }
+
+  #[tokio::test]
+  async fn test_oas30_parameter_override() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/parameter-override.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    let operation = &api.paths()[0].operations()[0];
+
+    let path_parameters = operation.path_parameters();
+    assert_eq!(path_parameters.len(), 1);
+
+    let query_parameters = operation.query_parameters();
+    assert_eq!(query_parameters.len(), 1);
+    assert_eq!(query_parameters[0].name(), "language");
+    assert!(query_parameters[0].required());
+    assert_eq!(
+      query_parameters[0].location(),
+      format!("{}/paths/~1noop~1%7Bid%7D/get/parameters/0", location)
+    );
+
+    let header_parameters = operation.header_parameters();
+    assert_eq!(header_parameters.len(), 1);
+    assert_eq!(
+      header_parameters[0].location(),
+      format!("{}/paths/~1noop~1%7Bid%7D/get/parameters/1", location)
+    );
+
+    let diagnostics = context.0.get_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].location.to_string(),
+      format!("{}/paths/~1noop~1%7Bid%7D/get/parameters/2", location)
+    );
+  }
+
+  #[tokio::test]
+  async fn test_oas30_operation_name() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/operation-name.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    let paths = api.paths();
+    let operation = &paths[0].operations()[0];
+    assert_eq!(operation.name(), "getUsersById");
+    assert_eq!(operation.operation_id(), Some("getUsersById".to_owned()));
+
+    let operation = &paths[0].operations()[1];
+    assert_eq!(operation.name(), "postUsers");
+    assert_eq!(operation.operation_id(), None);
+
+    // the synthesized name collides with the operationId of get /users
+    let operation = &paths[1].operations()[0];
+    assert_eq!(operation.name(), "getUsersById2");
+
+    let diagnostics = context.0.get_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+
+    context.set_operation_name_strategy(models::OperationNameStrategy::PathMethod);
+    let api = context.get_api_model(location).unwrap();
+
+    let paths = api.paths();
+    assert_eq!(paths[0].operations()[1].name(), "usersPost");
+    assert_eq!(paths[1].operations()[0].name(), "usersByIdGet");
+  }
+
+  #[tokio::test]
+  async fn test_multi_document() {
+    for parallelism in [1, 2, DEFAULT_PARALLELISM] {
+      let context = DocumentContextContainer::default();
+      context.register_well_known_factories();
+      context.set_parallelism(parallelism);
+
+      let progress = rc::Rc::new(RefCell::new(Vec::new()));
+      context.0.set_progress_handler(Some({
+        let progress = progress.clone();
+        Box::new(move |item: &LoadProgress| progress.borrow_mut().push(item.clone()))
+      }));
+
+      let location = "../../../fixtures/specifications/multi-document.yaml#";
+
+      context.load_from_location(location).await.unwrap();
+      assert!(context.get_diagnostics().is_empty());
+
+      /*
+      every document is loaded once, even though it is referenced more than
+      once
+      */
+      let progress = progress.borrow();
+      assert_eq!(progress.len(), 3);
+      assert_eq!(progress[0].location.to_string(), location);
+      assert_eq!(progress[2].loaded_count, 3);
+      assert_eq!(progress[2].discovered_count, 3);
+
+      let api = context.get_api_model(location).unwrap();
+      let operation = &api.paths()[0].operations()[0];
+      assert_eq!(operation.path_parameters().len(), 1);
+      assert_eq!(operation.query_parameters().len(), 1);
+      assert_eq!(operation.operation_results().len(), 2);
+    }
+  }
+
+  #[tokio::test]
+  async fn test_memory_fetcher() {
+    let api_data = r#"
+openapi: 3.0.0
+info:
+  title: memory
+  version: 0.1.0
+paths:
+  /users/{id}:
+    parameters:
+      - $ref: "parameters.yaml#/components/parameters/id"
+    get:
+      operationId: getUser
+      responses:
+        "204":
+          description: No Content
+"#;
+    let parameters_data = r#"
+openapi: 3.0.0
+info:
+  title: memory-parameters
+  version: 0.1.0
+paths: {}
+components:
+  parameters:
+    id:
+      name: id
+      in: path
+      required: true
+      schema:
+        type: string
+"#;
+
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let mut fetcher = MemoryFetcher::new();
+    fetcher.insert("memory://specifications/api.yaml", api_data);
+    context
+      .0
+      .register_fetcher("memory://", rc::Rc::new(fetcher));
+    /*
+    the longest prefix wins, so this document is not retrieved via the
+    fetcher that is registered above
+    */
+    context
+      .register_memory_document(
+        "memory://specifications/parameters.yaml",
+        parameters_data.to_owned(),
+      )
+      .unwrap();
+
+    let location = "memory://specifications/api.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    let api = context.get_api_model(location).unwrap();
+    let operation = &api.paths()[0].operations()[0];
+    assert_eq!(operation.path_parameters().len(), 1);
+
+    let location = "memory://specifications/missing.yaml#";
+    assert_eq!(
+      context.load_from_location(location).await,
+      Err(Error::FetchError)
+    );
+  }
+
+  #[tokio::test]
+  async fn test_component_reference_chain() {
+    let api_data = r##"
+openapi: 3.0.0
+info:
+  title: api
+  version: 0.1.0
+paths:
+  /items:
+    get:
+      parameters:
+        - $ref: "#/components/parameters/limit"
+      responses:
+        "200":
+          $ref: "#/components/responses/items"
+components:
+  parameters:
+    limit:
+      $ref: "other.yaml#/components/parameters/limit"
+  responses:
+    items:
+      description: Ok
+      headers:
+        x-total:
+          $ref: "headers.yaml#/components/headers/total"
+"##;
+    let other_data = r##"
+openapi: 3.0.0
+info:
+  title: other
+  version: 0.1.0
+paths: {}
+components:
+  parameters:
+    limit:
+      $ref: "#/components/parameters/maximum"
+    maximum:
+      name: limit
+      in: query
+      schema:
+        type: integer
+"##;
+    let headers_data = r##"
+openapi: 3.0.0
+info:
+  title: headers
+  version: 0.1.0
+paths: {}
+components:
+  headers:
+    total:
+      schema:
+        type: integer
+"##;
+
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    for (location, data) in [
+      ("memory://a/api.yaml", api_data),
+      ("memory://a/other.yaml", other_data),
+      ("memory://a/headers.yaml", headers_data),
+    ] {
+      context
+        .register_memory_document(location, data.to_owned())
+        .unwrap();
+    }
+
+    let location = "memory://a/api.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    let api = context.get_api_model(location).unwrap();
+    let operation = &api.paths()[0].operations()[0];
+    let query_parameters = operation.query_parameters();
+    assert_eq!(query_parameters.len(), 1);
+    assert_eq!(
+      query_parameters[0].location(),
+      "memory://a/other.yaml#/components/parameters/maximum"
+    );
+    let header_parameters = operation.operation_results()[0].header_parameters();
+    assert_eq!(header_parameters.len(), 1);
+    assert_eq!(
+      header_parameters[0].location(),
+      "memory://a/headers.yaml#/components/headers/total"
+    );
+  }
+
+  #[tokio::test]
+  async fn test_lockfile() {
+    let location = "memory://specifications/api.yaml#";
+    let make_context = |data: &str| {
+      let context = DocumentContextContainer::default();
+      context.register_well_known_factories();
+      context
+        .register_memory_document(location, data.to_owned())
+        .unwrap();
+      context
+    };
+    let data = "{ openapi: 3.0.0, info: { title: lockfile, version: 0.1.0 }, paths: {} }";
+
+    let context = make_context(data);
+    context.set_lockfile(None, LockfileMode::Update).unwrap();
+    context.load_from_location(location).await.unwrap();
+    let lockfile = context.get_lockfile().unwrap().unwrap();
+    assert_eq!(
+      Lockfile::from_json(&lockfile).unwrap().documents["memory://specifications/api.yaml"],
+      Lockfile::make_content_hash(data)
+    );
+
+    let context = make_context(data);
+    context
+      .set_lockfile(Some(lockfile.clone()), LockfileMode::Verify)
+      .unwrap();
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    let context = make_context(&data.replace("0.1.0", "0.2.0"));
+    context
+      .set_lockfile(Some(lockfile.clone()), LockfileMode::Verify)
+      .unwrap();
+    assert_eq!(
+      context.load_from_location(location).await,
+      Err(Error::LockfileMismatch)
+    );
+    assert!(context
+      .get_diagnostics()
+      .iter()
+      .any(|diagnostic| diagnostic.message().contains("does not match")));
+
+    let context = make_context(data);
+    context.set_lockfile(None, LockfileMode::Verify).unwrap();
+    assert_eq!(
+      context.load_from_location(location).await,
+      Err(Error::LockfileMismatch)
+    );
+
+    /*
+    pins of documents that are no longer retrieved are dropped when updating
+    */
+    let mut stale_lockfile = Lockfile::from_json(&lockfile).unwrap();
+    stale_lockfile.documents.insert(
+      "memory://specifications/removed.yaml".to_owned(),
+      Lockfile::make_content_hash("removed"),
+    );
+    let context = make_context(data);
+    context
+      .set_lockfile(
+        Some(stale_lockfile.to_json().unwrap()),
+        LockfileMode::Update,
+      )
+      .unwrap();
+    context.load_from_location(location).await.unwrap();
+    assert_eq!(context.get_lockfile().unwrap().unwrap(), lockfile);
+  }
+
+  #[tokio::test]
+  async fn test_fetch_policy() {
+    let location = "../../../fixtures/specifications/multi-document.yaml#";
+
+    async fn load(location: &str, fetch_policy: FetchPolicy) -> (Result<(), Error>, usize) {
+      let context = DocumentContextContainer::default();
+      context.register_well_known_factories();
+      context.0.set_fetch_policy(fetch_policy);
+
+      let result = context.load_from_location(location).await;
+      let violation_count = context
+        .get_diagnostics()
+        .iter()
+        .filter(|diagnostic| diagnostic.message().starts_with("fetch policy violation"))
+        .count();
+      (result, violation_count)
+    }
+
+    let fetch_policy = FetchPolicy {
+      allowed_hosts: Some(vec!["example.com".to_owned()]),
+      file_root: Some("../../../fixtures/specifications".to_owned()),
+      maximum_document_size: Some(1024),
+      maximum_document_count: Some(3),
+      maximum_depth: Some(1),
+      ..Default::default()
+    };
+    assert_eq!(load(location, fetch_policy.clone()).await, (Ok(()), 0));
+
+    let violation = (Err(Error::FetchPolicyViolation), 1);
+    assert_eq!(
+      load(
+        location,
+        FetchPolicy {
+          maximum_document_count: Some(2),
+          ..Default::default()
+        }
+      )
+      .await,
+      violation
+    );
+    assert_eq!(
+      load(
+        location,
+        FetchPolicy {
+          maximum_depth: Some(0),
+          ..Default::default()
+        }
+      )
+      .await,
+      (Err(Error::FetchPolicyViolation), 2)
+    );
+    assert_eq!(
+      load(
+        location,
+        FetchPolicy {
+          file_root: Some("../../../fixtures/specifications/multi-document".to_owned()),
+          ..Default::default()
+        }
+      )
+      .await,
+      violation
+    );
+    assert_eq!(
+      load(
+        location,
+        FetchPolicy {
+          maximum_document_size: Some(16),
+          ..Default::default()
+        }
+      )
+      .await,
+      violation
+    );
+    /*
+    the host is never contacted
+    */
+    assert_eq!(
+      load(
+        "http://specifications.invalid/api.yaml#",
+        FetchPolicy {
+          denied_hosts: vec!["invalid".to_owned()],
+          ..Default::default()
+        }
+      )
+      .await,
+      violation
+    );
+  }
+
+  #[tokio::test]
+  async fn test_fetch_policy_depth() {
+    /*
+    b.yaml is referenced by the api and by a.yaml, so it is within the
+    maximum depth
+    */
+    let api_data = r##"
+openapi: 3.0.0
+info:
+  title: api
+  version: 0.1.0
+paths:
+  /first:
+    parameters:
+      - $ref: "b.yaml#/components/parameters/b"
+  /second:
+    parameters:
+      - $ref: "a.yaml#/components/parameters/a"
+"##;
+    let a_data = r##"
+openapi: 3.0.0
+info:
+  title: a
+  version: 0.1.0
+paths: {}
+components:
+  parameters:
+    a:
+      $ref: "b.yaml#/components/parameters/b"
+"##;
+    let b_data = r##"
+openapi: 3.0.0
+info:
+  title: b
+  version: 0.1.0
+paths: {}
+components:
+  parameters:
+    b:
+      name: b
+      in: query
+      schema:
+        type: string
+"##;
+
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context.0.set_fetch_policy(FetchPolicy {
+      maximum_depth: Some(1),
+      ..Default::default()
+    });
+    for (location, data) in [
+      ("memory://depth/api.yaml", api_data),
+      ("memory://depth/a.yaml", a_data),
+      ("memory://depth/b.yaml", b_data),
+    ] {
+      context
+        .register_memory_document(location, data.to_owned())
+        .unwrap();
+    }
+
+    let location = "memory://depth/api.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    context.bundle(location, OutputFormat::Json).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_file_scheme() {
+    let path = std::fs::canonicalize("../../../fixtures/specifications/echo.yaml").unwrap();
+    let location = format!("file://{}#", path.to_str().unwrap());
+
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context.0.set_fetch_policy(FetchPolicy {
+      file_root: Some("../../../fixtures/specifications".to_owned()),
+      ..Default::default()
+    });
+
+    context.load_from_location(&location).await.unwrap();
+    context.get_api_model(&location).unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_bundle() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/bundle/api.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let data = context.bundle(location, OutputFormat::Json).await.unwrap();
+    let node: serde_json::Value = serde_json::from_str(&data).unwrap();
+
+    let get_reference = |pointer: &str| node.pointer(pointer).and_then(|node| node.as_str());
+    assert_eq!(
+      get_reference("/paths/~1users~1{id}/parameters/0/$ref"),
+      Some("#/components/parameters/id")
+    );
+    assert_eq!(
+      get_reference("/components/parameters/id/schema/$ref"),
+      Some("#/components/schemas/id")
+    );
+    /*
+    the name user is already taken by a schema in the bundled document
+    */
+    assert_eq!(
+      get_reference("/paths/~1users~1{id}/get/responses/200/content/application~1json/schema/$ref"),
+      Some("#/components/schemas/user2")
+    );
+    assert_eq!(
+      get_reference("/components/schemas/user2/properties/address/$ref"),
+      Some("#/components/schemas/address")
+    );
+    assert_eq!(
+      get_reference("/components/schemas/user2/properties/manager/$ref"),
+      Some("#/components/schemas/user2")
+    );
+    assert!(node
+      .pointer("/components/schemas/user/properties/id")
+      .is_some());
+
+    /*
+    the bundled document is self-contained and can be loaded on its own
+    */
+    let bundled_location: NodeLocation = "memory://bundle/api.yaml#".parse().unwrap();
+    assert!(find_node_references(&bundled_location, &node)
+      .iter()
+      .all(|reference| reference.target_location.set_root() == bundled_location.set_root()));
+
+    let data = context.bundle(location, OutputFormat::Yaml).await.unwrap();
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context
+      .register_memory_document(&bundled_location.to_string(), data)
+      .unwrap();
+    context
+      .load_from_location(&bundled_location.to_string())
+      .await
+      .unwrap();
+    assert!(context.get_diagnostics().is_empty());
+    let api = context
+      .get_api_model(&bundled_location.to_string())
+      .unwrap();
+    assert_eq!(api.paths()[0].operations().len(), 2);
+    assert_eq!(
+      api.paths()[0].operations()[0].path_parameters()[0].name(),
+      "id"
+    );
+  }
+
+  #[tokio::test]
+  async fn test_bundle_fetch_policy() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/bundle/api.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    /*
+    the referenced nodes are already in the cache, but following the references
+    is still a violation of the fetch policy
+    */
+    context.0.set_fetch_policy(FetchPolicy {
+      maximum_depth: Some(0),
+      ..Default::default()
+    });
+    assert_eq!(
+      context.bundle(location, OutputFormat::Json).await,
+      Err(Error::FetchPolicyViolation)
+    );
+    assert_eq!(
+      context.dereference(location, OutputFormat::Json).await,
+      Err(Error::FetchPolicyViolation)
+    );
+    assert!(context
+      .get_diagnostics()
+      .iter()
+      .any(|diagnostic| diagnostic.message().starts_with("fetch policy violation")));
+  }
+
+  #[tokio::test]
+  async fn test_dereference() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/bundle/api.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let data = context
+      .dereference(location, OutputFormat::Json)
+      .await
+      .unwrap();
+    let node: serde_json::Value = serde_json::from_str(&data).unwrap();
+
+    let schema_pointer = "/paths/~1users~1{id}/get/responses/200/content/application~1json/schema";
+    assert_eq!(
+      node
+        .pointer(&format!("{}/properties/address/type", schema_pointer))
+        .and_then(|node| node.as_str()),
+      Some("object")
+    );
+    /*
+    the recursive manager schema is the only reference that is left
+    */
+    let dereferenced_location: NodeLocation = "memory://dereference/api.yaml#".parse().unwrap();
+    let references = find_node_references(&dereferenced_location, &node);
+    assert_eq!(references.len(), 1);
+    assert_eq!(
+      references[0].source_location,
+      dereferenced_location.push_pointer(
+        schema_pointer
+          .split('/')
+          .skip(1)
+          .chain(["properties", "manager"])
+          .map(|member| member.replace("~1", "/"))
+          .collect()
+      )
+    );
+    assert_eq!(
+      references[0].target_location,
+      dereferenced_location.push_pointer(
+        schema_pointer
+          .split('/')
+          .skip(1)
+          .map(|member| member.replace("~1", "/"))
+          .collect()
+      )
+    );
+
+    let location = "../../../fixtures/specifications/reference-chain.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let data = context
+      .dereference(location, OutputFormat::Yaml)
+      .await
+      .unwrap();
+    let node: serde_json::Value = serde_yaml::from_str(&data).unwrap();
+
+    assert_eq!(
+      node
+        .pointer("/paths/~1nodes/get/parameters/0/name")
+        .and_then(|node| node.as_str()),
+      Some("depth")
+    );
+    assert_eq!(
+      node
+        .pointer("/components/schemas/node/properties/children/items/$ref")
+        .and_then(|node| node.as_str()),
+      Some("#/components/schemas/node")
+    );
+    assert_eq!(
+      node
+        .pointer("/components/schemas/node/properties/name/type")
+        .and_then(|node| node.as_str()),
+      Some("string")
+    );
+  }
+
+  #[tokio::test]
+  async fn test_dereference_reference_members() {
+    let data = r##"
+      openapi: VERSION
+      info: { title: members, version: 0.1.0 }
+      paths:
+        /users/{id}:
+          parameters:
+            - $ref: "#/components/parameters/id"
+              description: The id of the user
+      components:
+        parameters:
+          id:
+            name: id
+            in: path
+            required: true
+            description: An id
+            schema:
+              type: string
+    "##;
+    let pointer = "/paths/~1users~1{id}/parameters/0/description";
+
+    /*
+    only 3.1 lets the members next to a reference override the referenced node
+    */
+    for (version, description, diagnostic_count) in
+      [("3.1.0", "The id of the user", 0), ("3.0.3", "An id", 1)]
+    {
+      let context = DocumentContextContainer::default();
+      context.register_well_known_factories();
+      let location = format!("memory://members/{}.yaml#", version);
+      context
+        .register_memory_document(&location, data.replace("VERSION", version))
+        .unwrap();
+
+      context.load_from_location(&location).await.unwrap();
+      let diagnostic_count = context.get_diagnostics().len() + diagnostic_count;
+      let data = context
+        .dereference(&location, OutputFormat::Json)
+        .await
+        .unwrap();
+      let node: serde_json::Value = serde_json::from_str(&data).unwrap();
+
+      assert_eq!(
+        node.pointer(pointer).and_then(|node| node.as_str()),
+        Some(description)
+      );
+      let diagnostics = context.get_diagnostics();
+      assert_eq!(diagnostics.len(), diagnostic_count);
+      if version == "3.0.3" {
+        assert_eq!(
+          diagnostics.last().unwrap().location(),
+          format!("{}/paths/~1users~1%7Bid%7D/parameters/0", location)
+        );
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn test_convert_swagger2() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/convert/swagger2.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    let data = context
+      .convert_swagger2(location, DocumentType::OpenApiV30, OutputFormat::Json)
+      .await
+      .unwrap();
+    let node: serde_json::Value = serde_json::from_str(&data).unwrap();
+
+    let get_string = |pointer: &str| node.pointer(pointer).and_then(|node| node.as_str());
+    assert_eq!(get_string("/openapi"), Some("3.0.3"));
+    assert_eq!(
+      get_string("/servers/0/url"),
+      Some("https://pets.example.com/v1")
+    );
+    assert_eq!(
+      get_string("/servers/1/url"),
+      Some("http://pets.example.com/v1")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets/get/parameters/0/$ref"),
+      Some("#/components/parameters/limit")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets/get/parameters/1/style"),
+      Some("pipeDelimited")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets/get/responses/200/content/application~1json/schema/items/$ref"),
+      Some("#/components/schemas/pet")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets/get/responses/200/content/application~1json/example/0/name"),
+      Some("rex")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets/get/responses/200/headers/x-total/schema/type"),
+      Some("integer")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets/post/requestBody/$ref"),
+      Some("#/components/requestBodies/pet")
+    );
+    /*
+    the operation has its own media types, so the request body is not a
+    reference to the component
+    */
+    assert_eq!(
+      get_string("/paths/~1pets~1{id}/put/requestBody/content/application~1xml/schema/$ref"),
+      Some("#/components/schemas/pet")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets~1{id}/parameters/0/schema/type"),
+      Some("string")
+    );
+    let form_pointer =
+      "/paths/~1pets~1{id}~1photo/post/requestBody/content/multipart~1form-data/schema";
+    assert_eq!(
+      get_string(&format!("{}/properties/photo/format", form_pointer)),
+      Some("binary")
+    );
+    assert_eq!(
+      get_string(&format!("{}/properties/caption/type", form_pointer)),
+      Some("string")
+    );
+    assert_eq!(
+      get_string(&format!("{}/required/0", form_pointer)),
+      Some("photo")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets~1{id}~1photo/post/servers/0/url"),
+      Some("https://pets.example.com/v1")
+    );
+    assert_eq!(
+      get_string("/paths/~1pets~1{id}~1photo/post/responses/200/content/image~1png/schema/format"),
+      Some("binary")
+    );
+    assert_eq!(
+      get_string("/components/securitySchemes/basic/scheme"),
+      Some("basic")
+    );
+    assert_eq!(
+      get_string("/components/securitySchemes/oauth/flows/authorizationCode/tokenUrl"),
+      Some("https://pets.example.com/token")
+    );
+    assert_eq!(
+      get_string("/components/schemas/pet/discriminator/propertyName"),
+      Some("kind")
+    );
+    assert_eq!(
+      node
+        .pointer("/components/schemas/pet/properties/owner/nullable")
+        .and_then(|node| node.as_bool()),
+      Some(true)
+    );
+
+    /*
+    header parameters can not have the form style
+    */
+    assert_eq!(
+      get_string("/paths/~1pets/get/parameters/3/style"),
+      Some("simple")
+    );
+
+    /*
+    the name of the body parameter is kept as an extension
+    */
+    assert_eq!(
+      get_string("/components/requestBodies/pet/x-codegen-request-body-name"),
+      Some("pet")
+    );
+
+    /*
+    the tsv collection format has no equivalent, space delimited header
+    parameters are not allowed and the discriminator changed meaning
+    */
+    let diagnostics = context.get_diagnostics();
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(
+      diagnostics[0].location(),
+      "../../../fixtures/convert/swagger2.yaml#/definitions/pet/discriminator"
+    );
+    assert_eq!(
+      diagnostics[1].location(),
+      "../../../fixtures/convert/swagger2.yaml#/paths/~1pets/get/parameters/2"
+    );
+    assert_eq!(
+      diagnostics[2].location(),
+      "../../../fixtures/convert/swagger2.yaml#/paths/~1pets/get/parameters/3"
+    );
+
+    /*
+    the converted document is a valid OpenAPI 3.0 document
+    */
+    let converted_location = "memory://convert/openapi.yaml#";
+    let data = context
+      .convert_swagger2(location, DocumentType::OpenApiV30, OutputFormat::Yaml)
+      .await
+      .unwrap();
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context
+      .register_memory_document(converted_location, data)
+      .unwrap();
+    context
+      .load_from_location(converted_location)
+      .await
+      .unwrap();
+    assert!(context.get_diagnostics().is_empty());
+    let api = context.get_api_model(converted_location).unwrap();
+    assert_eq!(api.paths().len(), 3);
+  }
+
+  #[tokio::test]
+  async fn test_upgrade_oas30() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/upgrade/oas30.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    let data = context
+      .upgrade_oas30(location, OutputFormat::Json)
+      .await
+      .unwrap();
+    let node: serde_json::Value = serde_json::from_str(&data).unwrap();
+
+    assert_eq!(node.pointer("/openapi"), Some(&serde_json::json!("3.1.0")));
+    let schema_pointer = "/paths/~1pets/get/parameters/0/schema";
+    assert_eq!(
+      node.pointer(&format!("{}/exclusiveMinimum", schema_pointer)),
+      Some(&serde_json::json!(0))
+    );
+    assert_eq!(node.pointer(&format!("{}/minimum", schema_pointer)), None);
+    assert_eq!(
+      node.pointer(&format!("{}/maximum", schema_pointer)),
+      Some(&serde_json::json!(100))
+    );
+    assert_eq!(
+      node.pointer(&format!("{}/exclusiveMaximum", schema_pointer)),
+      None
+    );
+    /*
+    the example of a parameter is still valid in 3.1
+    */
+    assert_eq!(
+      node.pointer("/paths/~1pets/get/parameters/0/example"),
+      Some(&serde_json::json!(10))
+    );
+    assert_eq!(
+      node.pointer("/paths/~1pets/get/responses/200/content/application~1json/schema/items/$ref"),
+      Some(&serde_json::json!("#/components/schemas/pet"))
+    );
+    let form_pointer =
+      "/paths/~1pets~1{id}~1photo/put/requestBody/content/multipart~1form-data/schema/properties";
+    assert_eq!(
+      node.pointer(&format!("{}/photo", form_pointer)),
+      Some(&serde_json::json!({
+        "type": "string",
+        "contentMediaType": "application/octet-stream",
+      }))
+    );
+    assert_eq!(
+      node.pointer(&format!("{}/thumbnail", form_pointer)),
+      Some(&serde_json::json!({
+        "type": "string",
+        "contentEncoding": "base64",
+      }))
+    );
+    assert_eq!(
+      node.pointer("/components/schemas/pet/examples"),
+      Some(&serde_json::json!([{ "name": "rex" }]))
+    );
+    assert_eq!(
+      node.pointer("/components/schemas/pet/properties/kind"),
+      Some(&serde_json::json!({
+        "type": ["string", "null"],
+        "enum": ["cat", "dog", null],
+      }))
+    );
+
+    /*
+    the schema in a separate file is bundled and upgraded too
+    */
+    assert_eq!(
+      node.pointer("/paths/~1pets/get/responses/default/content/application~1json/schema/$ref"),
+      Some(&serde_json::json!("#/components/schemas/error"))
+    );
+    assert_eq!(
+      node.pointer("/components/schemas/error/properties"),
+      Some(&serde_json::json!({
+        "code": { "type": "integer", "exclusiveMinimum": 0 },
+        "message": { "type": ["string", "null"] },
+      }))
+    );
+
+    /*
+    the nullable owner has no type, so that could not be converted
+    */
+    let diagnostics = context.get_diagnostics();
+    assert_eq!(diagnostics.len(), 8);
+    assert!(diagnostics.iter().any(|diagnostic| diagnostic.location()
+      == "../../../fixtures/upgrade/error.yaml#/properties/message"));
+    let warnings: Vec<_> = diagnostics
+      .iter()
+      .filter(|diagnostic| diagnostic.severity() == DiagnosticSeverity::Warning)
+      .collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+      warnings[0].location(),
+      "../../../fixtures/upgrade/oas30.yaml#/components/schemas/pet/properties/owner"
+    );
+
+    /*
+    the upgraded document is a valid OpenAPI 3.1 document
+    */
+    let upgraded_location = "memory://upgrade/openapi.yaml#";
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context
+      .register_memory_document(upgraded_location, data)
+      .unwrap();
+    context.load_from_location(upgraded_location).await.unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    /*
+    a swagger 2.0 document is converted to 3.0 and then upgraded to 3.1
+    */
+    let location = "../../../fixtures/convert/swagger2.yaml#";
+    context.load_from_location(location).await.unwrap();
+    let data = context
+      .convert_swagger2(location, DocumentType::OpenApiV31, OutputFormat::Json)
+      .await
+      .unwrap();
+    let node: serde_json::Value = serde_json::from_str(&data).unwrap();
+    assert_eq!(node.pointer("/openapi"), Some(&serde_json::json!("3.1.0")));
+    assert_eq!(
+      node.pointer("/components/schemas/pet/properties/owner/type"),
+      Some(&serde_json::json!(["string", "null"]))
+    );
+
+    /*
+    changes are reported on the node in the swagger document
+    */
+    assert!(context
+      .get_diagnostics()
+      .iter()
+      .any(|diagnostic| diagnostic.location()
+        == "../../../fixtures/convert/swagger2.yaml#/definitions/pet/properties/owner"
+        && diagnostic.message() == "converted nullable to a type array"));
+  }
+
+  #[tokio::test]
+  async fn test_overlay() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/overlay/api.yaml#";
+    let overlay_location = "../../../fixtures/overlay/overlay.yaml#";
+
+    context
+      .set_overlays(location, vec![overlay_location.to_owned()])
+      .unwrap();
+    context.set_lockfile(None, LockfileMode::Update).unwrap();
+    context.load_from_location(location).await.unwrap();
+
+    /*
+    the last action selects nothing
+    */
+    let diagnostics = context.get_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity(), DiagnosticSeverity::Warning);
+    assert_eq!(
+      diagnostics[0].location(),
+      "../../../fixtures/overlay/overlay.yaml#/actions/2"
+    );
+
+    let node = context.0.get_node(&location.parse().unwrap()).unwrap();
+    assert_eq!(
+      node.pointer("/info/description"),
+      Some(&serde_json::json!("The public pets api"))
+    );
+
+    let api = context.get_api_model(location).unwrap();
+    let operation_names: Vec<_> = api
+      .paths()
+      .iter()
+      .flat_map(|path| path.operations())
+      .map(|operation| operation.name())
+      .collect();
+    assert_eq!(operation_names, vec!["listPets"]);
+
+    /*
+    both the overlay and the document it is applied to are inputs
+    */
+    let lockfile = context.get_lockfile().unwrap().unwrap();
+    let lockfile = Lockfile::from_json(&lockfile).unwrap();
+    assert_eq!(lockfile.documents.len(), 2);
+    assert!(lockfile
+      .documents
+      .keys()
+      .any(|key| key.ends_with("overlay/overlay.yaml")));
+  }
+
+  #[tokio::test]
+  async fn test_overlay_cached() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/overlay/api.yaml#";
+    let overlay_location = "../../../fixtures/overlay/overlay.yaml#";
+
+    /*
+    the node is in the cache before the overlays are configured, like when it
+    is loaded by a transform
+    */
+    let retrieval_location: NodeLocation = location.parse().unwrap();
+    context.0.load_node(&retrieval_location).await.unwrap();
+    let title_location =
+      retrieval_location.push_pointer(vec!["info".to_owned(), "title".to_owned()]);
+    assert_eq!(
+      context.0.get_node_position(&title_location).unwrap().line,
+      4
+    );
+
+    context
+      .set_overlays(location, vec![overlay_location.to_owned()])
+      .unwrap();
+    context.load_from_location(location).await.unwrap();
+
+    let api = context.get_api_model(location).unwrap();
+    let operation_names: Vec<_> = api
+      .paths()
+      .iter()
+      .flat_map(|path| path.operations())
+      .map(|operation| operation.name())
+      .collect();
+    assert_eq!(operation_names, vec!["listPets"]);
+
+    /*
+    the info node is changed by the overlay, so its members get the position
+    of the info node
+    */
+    assert_eq!(
+      context.0.get_node_position(&title_location).unwrap().line,
+      3
+    );
+
+    /*
+    the document is made, so other overlays are not applied any more
+    */
+    context.set_overlays(location, Vec::new()).unwrap();
+    assert!(context
+      .get_diagnostics()
+      .iter()
+      .any(
+        |diagnostic| diagnostic.location() == "../../../fixtures/overlay/api.yaml#"
+          && diagnostic.severity() == DiagnosticSeverity::Warning
+      ));
+  }
+
+  #[tokio::test]
+  async fn test_query() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/overlay/api.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+
+    assert_eq!(
+      context
+        .query(location, "$.paths.*[?@.tags[0] == 'internal']")
+        .unwrap(),
+      vec![
+        "../../../fixtures/overlay/api.yaml#/paths/~1admin/get",
+        "../../../fixtures/overlay/api.yaml#/paths/~1pets/delete",
+      ]
+    );
+    /*
+    the root of the query is the node at the location
+    */
+    assert_eq!(
+      context
+        .query(
+          "../../../fixtures/overlay/api.yaml#/paths/~1pets",
+          "$[?count(@.tags[?@ == 'public']) > 0].operationId"
+        )
+        .unwrap(),
+      vec!["../../../fixtures/overlay/api.yaml#/paths/~1pets/get/operationId"]
+    );
+    assert_eq!(
+      context.query(location, "$.paths[").map(|_| ()),
+      Err(Error::ParseJsonPathFailed)
+    );
+  }
+
+  #[tokio::test]
+  async fn test_broken_reference() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/malformed/broken-reference.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    let paths = api.paths();
+    assert_eq!(paths[0].pattern(), "/broken");
+    assert_eq!(paths[0].operations().len(), 0);
+    assert_eq!(paths[1].operations().len(), 1);
+
+    let diagnostics = context.get_diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|diagnostic| {
+      diagnostic.severity() == DiagnosticSeverity::Error
+        && diagnostic.cause() == Some(Error::NotFound)
+    }));
+    assert_eq!(
+      diagnostics[0].location(),
+      format!("{}/paths/~1broken/get", location)
+    );
+    assert_eq!(
+      diagnostics[1].location(),
+      format!("{}/paths/~1broken/get/parameters/0", location)
+    );
+
+    let position = context
+      .get_node_position(&diagnostics[1].location())
+      .unwrap();
+    assert_eq!(
+      position.document_location(),
+      "../../../fixtures/malformed/broken-reference.yaml"
+    );
+    assert_eq!(position.line(), 18);
+    assert_eq!(position.column(), 11);
+  }
+
+  #[tokio::test]
+  async fn test_reference_chain() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/reference-chain.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+    assert!(context.get_diagnostics().is_empty());
+
+    let operation = &api.paths()[0].operations()[0];
+    let query_parameters = operation.query_parameters();
+    assert_eq!(query_parameters.len(), 1);
+    assert_eq!(query_parameters[0].name(), "depth");
+    assert_eq!(
+      query_parameters[0].location(),
+      format!("{}/components/parameters/maximum-depth", location)
+    );
+    let operation_results = operation.operation_results();
+    assert_eq!(operation_results.len(), 1);
+    assert_eq!(operation_results[0].description(), Some("Ok".to_owned()));
+
+    let recursive_schema_locations: Vec<_> = context
+      .get_schemas()
+      .into_iter()
+      .filter(|schema| schema.recursive())
+      .map(|schema| schema.schema_location())
+      .collect();
+    assert_eq!(
+      recursive_schema_locations,
+      vec![format!("{}/components/schemas/node", location)]
+    );
+  }
+
+  #[tokio::test]
+  async fn test_reference_cycle() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/malformed/reference-cycle.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    let paths = api.paths();
+    assert_eq!(paths[0].pattern(), "/cycle");
+    assert_eq!(paths[0].operations().len(), 0);
+    assert_eq!(paths[1].operations().len(), 1);
+
+    let diagnostic = context
+      .get_diagnostics()
+      .into_iter()
+      .find(|diagnostic| diagnostic.message().starts_with("reference cycle"))
+      .unwrap();
+    assert_eq!(diagnostic.cause(), Some(Error::ReferenceCycle));
+    assert_eq!(
+      diagnostic.location(),
+      format!("{}/paths/~1cycle/get/parameters/0", location)
+    );
+    assert!(diagnostic.message().contains(&format!(
+      "{0}/components/parameters/a -> {0}/components/parameters/b -> {0}/components/parameters/a",
+      location
+    )));
+  }
+
+  #[tokio::test]
+  async fn test_reference_cycle_swagger2() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/malformed/reference-cycle20.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    let paths = api.paths();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].pattern(), "/noop");
+
+    let diagnostic = context
+      .get_diagnostics()
+      .into_iter()
+      .find(|diagnostic| diagnostic.message().starts_with("reference cycle"))
+      .unwrap();
+    assert_eq!(diagnostic.cause(), Some(Error::ReferenceCycle));
+    assert_eq!(diagnostic.location(), format!("{}/paths/~1a", location));
+    assert!(diagnostic.message().contains(&format!(
+      "{0}/paths/~1a -> {0}/paths/~1b -> {0}/paths/~1a",
+      location
+    )));
+  }
+
+  #[tokio::test]
+  async fn test_missing_document() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/malformed/missing.yaml#";
+
+    assert!(context.load_from_location(location).await.is_err());
+    assert!(context.get_api_model(location).is_err());
+
+    let diagnostics = context.get_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].location(), location);
+    assert_eq!(diagnostics[0].severity(), DiagnosticSeverity::Error);
+  }
+
+  #[tokio::test]
+  async fn test_invalid_document() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/malformed/invalid-document.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    assert!(context.get_api_model(location).is_ok());
+
+    let diagnostics = context.get_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+      diagnostics[0].location(),
+      format!("{}/paths/~1noop/get/deprecated", location)
+    );
+    assert_eq!(diagnostics[0].cause(), Some(Error::ValidationFailed));
+
+    context.set_strict(true);
+    assert!(context.get_api_model(location).is_err());
+  }
+
+  #[tokio::test]
+  async fn test_lint() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context.register_well_known_rules();
+
+    let location = "../../../fixtures/specifications/lint.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    context.lint(location).unwrap();
+
+    let actual: Vec<_> = context
+      .get_diagnostics()
+      .into_iter()
+      .map(|diagnostic| {
+        /*
+        the rule name is the start of the message, two rules report on the
+        same operation
+        */
+        let message = diagnostic.message();
+        let rule_name = message.split(':').next().unwrap_or_default().to_owned();
+        (diagnostic.location(), diagnostic.severity(), rule_name)
+      })
+      .collect();
+    let expected = vec![
+      (
+        format!("{}/components/schemas/Unused", location),
+        DiagnosticSeverity::Warning,
+        "unused-components".to_owned(),
+      ),
+      (
+        format!("{}/components/securitySchemes/apiKey", location),
+        DiagnosticSeverity::Warning,
+        "security-scheme-description".to_owned(),
+      ),
+      (
+        format!("{}/components/securitySchemes/basic", location),
+        DiagnosticSeverity::Warning,
+        "unused-components".to_owned(),
+      ),
+      (
+        format!("{}/paths/~1userGroups~1%7Bid%7D", location),
+        DiagnosticSeverity::Warning,
+        "kebab-case-paths".to_owned(),
+      ),
+      (
+        format!("{}/paths/~1userGroups~1%7Bid%7D/delete", location),
+        DiagnosticSeverity::Warning,
+        "operation-4xx-response".to_owned(),
+      ),
+      (
+        format!("{}/paths/~1userGroups~1%7Bid%7D/delete", location),
+        DiagnosticSeverity::Warning,
+        "operation-id".to_owned(),
+      ),
+    ];
+    assert_eq!(actual.len(), expected.len());
+    for expected in expected {
+      assert!(actual.contains(&expected), "{:?}", expected);
+    }
+
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+    context.register_well_known_rules();
+    context.set_rule_severity("unused-components", None);
+    context.set_rule_severity("kebab-case-paths", Some(DiagnosticSeverity::Error));
+
+    context.load_from_location(location).await.unwrap();
+    context.lint(location).unwrap();
+
+    let diagnostics = context.get_diagnostics();
+    assert!(diagnostics
+      .iter()
+      .all(|diagnostic| !diagnostic.message().starts_with("unused-components")));
+    assert!(diagnostics.iter().any(|diagnostic| {
+      diagnostic.message().starts_with("kebab-case-paths")
+        && diagnostic.severity() == DiagnosticSeverity::Error
+    }));
+  }
+
+  /**
+  every entry point should return an error (or report a diagnostic) for
+  malformed input, but never panic
+  */
+  #[tokio::test]
+  async fn test_malformed() {
+    let mut locations: Vec<_> = std::fs::read_dir("../../../fixtures/malformed")
+      .unwrap()
+      .map(|entry| entry.unwrap().path())
+      .map(|path| format!("{}#", path.to_str().unwrap()))
+      .collect();
+    locations.sort();
+    locations.extend([
+      "".to_owned(),
+      "#/%zz".to_owned(),
+      "http://[::1".to_owned(),
+      "../../../fixtures/malformed/missing.yaml#".to_owned(),
+    ]);
+
+    for location in locations {
+      let context = DocumentContextContainer::default();
+      context.register_well_known_factories();
+      context.register_well_known_rules();
+
+      let result = context.load_from_location(&location).await;
+      let _ = context.get_api_model(&location);
+      let _ = context.lint(&location);
+      let _ = context.bundle(&location, OutputFormat::Json).await;
+      let _ = context.dereference(&location, OutputFormat::Json).await;
+      let _ = context
+        .convert_swagger2(&location, DocumentType::OpenApiV31, OutputFormat::Json)
+        .await;
+      let _ = context.upgrade_oas30(&location, OutputFormat::Json).await;
+      let _ = context.query(&location, "$..*");
+      context.set_strict(true);
+      let _ = context.get_api_model(&location);
+      let _ = context.get_schemas();
+      let _ = context.get_node_position(&location);
+
+      let diagnostics = context.get_diagnostics();
+      for diagnostic in &diagnostics {
+        let _ = context.get_node_position(&diagnostic.location());
+      }
+      if result.is_err() {
+        assert!(!diagnostics.is_empty() || location.parse::<NodeLocation>().is_err());
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn test_concurrent_load() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/reference-chain.yaml#";
+
+    let (result, other_result) = tokio::join!(
+      context.load_from_location(location),
+      context.load_from_location(location)
+    );
+    result.unwrap();
+    other_result.unwrap();
+
+    context.get_api_model(location).unwrap();
+    assert!(context.get_diagnostics().is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_unnamed_parameter() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/malformed/unnamed-parameter.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+    assert_eq!(api.paths()[0].operations().len(), 0);
+
+    assert!(context
+      .get_diagnostics()
+      .iter()
+      .any(|diagnostic| diagnostic.cause() == Some(Error::MissingField)));
+  }
+
+  #[tokio::test]
+  async fn test_relative_server_urls() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "https://api.example.com/specifications/api.yaml#";
+    context
+      .register_memory_document(
+        location,
+        r#"
+          openapi: 3.0.3
+          info: { title: servers, version: 0.1.0 }
+          servers:
+            - url: v1
+          paths:
+            /users:
+              $ref: "https://paths.example.com/specifications/users.yaml#/paths/~1users"
+        "#
+        .to_owned(),
+      )
+      .unwrap();
+    context
+      .register_memory_document(
+        "https://paths.example.com/specifications/users.yaml#",
+        r#"
+          openapi: 3.0.3
+          info: { title: users, version: 0.1.0 }
+          paths:
+            /users:
+              servers:
+                - url: /v2
+              get:
+                operationId: listUsers
+                servers:
+                  - url: v3
+                responses:
+                  "204":
+                    description: No Content
+        "#
+        .to_owned(),
+      )
+      .unwrap();
+
+    /*
+    relative urls are resolved against the document that defines the server
+    */
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+    assert_eq!(
+      api.servers()[0].url(),
+      "https://api.example.com/specifications/v1"
+    );
+
+    let paths = api.paths();
+    assert_eq!(paths[0].servers()[0].url(), "https://paths.example.com/v2");
+    assert_eq!(
+      paths[0].operations()[0].servers()[0].url(),
+      "https://paths.example.com/specifications/v3"
+    );
+  }
+
+  #[tokio::test]
+  async fn test_oas31() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/echo31.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    assert_eq!(api.location(), location.to_string());
+
+    let servers = api.servers();
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0].url(), "https://{environment}.example.com/v1");
+    assert_eq!(servers[0].description(), Some("echo server".to_owned()));
+
+    let variables = servers[0].variables();
+    assert_eq!(variables.len(), 1);
+    assert_eq!(variables[0].name(), "environment");
+    assert_eq!(variables[0].default(), "api");
+    assert_eq!(variables[0].r#enum(), vec!["api", "staging"]);
+
+    let paths = api.paths();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].servers()[0].url(), servers[0].url());
+
+    let operations = paths[0].operations();
+    assert_eq!(operations.len(), 1);
+
+    let operation = &operations[0];
+    assert_eq!(operation.name(), "echo");
+    assert_eq!(operation.servers()[0].url(), servers[0].url());
+    assert_eq!(operation.query_parameters().len(), 1);
+
+    let operation_results = operation.operation_results();
+    assert_eq!(operation_results.len(), 1);
+    assert_eq!(
+      operation_results[0].description(),
+      Some("Echoed value".to_owned())
+    );
+    assert_eq!(operation_results[0].header_parameters().len(), 1);
+
+    let webhooks = api.webhooks();
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(webhooks[0].name(), "echoed");
+
+    let operations = webhooks[0].operations();
+    assert_eq!(operations.len(), 1);
+    assert_eq!(operations[0].name(), "echoed");
+    assert_eq!(operations[0].bodies().len(), 1);
+
+    let schemas = context.get_schemas();
+    assert!(schemas.iter().any(|schema| schema.schema_location()
+      == format!(
+        "{}/webhooks/echoed/post/requestBody/content/application~1json/schema",
+        location
+      )));
+    assert!(
+      schemas.iter().all(
+        |schema| schema.default_schema_id() == "https://spec.openapis.org/oas/3.1/dialect/base"
+      )
+    );
+    assert!(schemas.iter().any(|schema| schema.schema_location()
+      == "../../../fixtures/specifications/echo31.yaml#/components/responses/echo-response/content/application~1json/schema"));
+  }
+
+  #[tokio::test]
+  async fn test_swagger2() {
+    let context = DocumentContextContainer::default();
+    context.register_well_known_factories();
+
+    let location = "../../../fixtures/specifications/echo20.yaml#";
+
+    context.load_from_location(location).await.unwrap();
+    let api = context.get_api_model(location).unwrap();
+
+    assert_eq!(api.location(), location.to_string());
+
+    let authentication = api.authentication();
+    assert_eq!(authentication.len(), 1);
+    assert_eq!(authentication[0].r#type(), "http");
+    assert_eq!(authentication[0].scheme(), Some("basic".to_owned()));
+
+    let servers = api.servers();
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0].url(), "https://echo.example.com/v1");
+
+    let paths = api.paths();
+    assert_eq!(paths.len(), 2);
+
+    let operation = &paths[0].operations()[0];
+    assert_eq!(operation.name(), "echo");
+    assert_eq!(operation.query_parameters().len(), 1);
+    assert_eq!(
+      operation.query_parameters()[0].schema_id(),
+      Some(format!("{}/parameters/value-parameter", location))
+    );
+
+    let bodies = operation.bodies();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(bodies[0].content_type(), "application/json");
+    assert_eq!(
+      bodies[0].schema_id(),
+      Some(format!(
+        "{}/paths/~1echo/post/parameters/0/schema",
+        location
+      ))
+    );
+
+    let operation_results = operation.operation_results();
+    assert_eq!(operation_results.len(), 1);
+    assert_eq!(operation_results[0].header_parameters().len(), 1);
+    assert_eq!(operation_results[0].bodies().len(), 1);
+
+    let operation = &paths[1].operations()[0];
+    assert_eq!(operation.name(), "upload");
+    let bodies = operation.bodies();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(bodies[0].content_type(), "multipart/form-data");
+    let form_schema_location = format!("{}/paths/~1upload/post", location);
+    assert_eq!(bodies[0].schema_id(), Some(form_schema_location.clone()));
+
+    let schemas = context.get_schemas();
+    let get_schema_node = |schema_location: &str| {
+      schemas
+        .iter()
+        .find(|schema| schema.schema_location() == schema_location)
+        .map(|schema| schema.schema_node())
+    };
+    assert_eq!(
+      get_schema_node(&format!("{}/definitions/message", location)),
+      Some(None)
+    );
+
+    /*
+    parameters, headers and forms have a derived schema with only schema
+    keywords
+    */
+    let get_derived_schema_node = |schema_location: &str| {
+      get_schema_node(schema_location)
+        .flatten()
+        .map(|schema_node| serde_json::from_str::<serde_json::Value>(&schema_node).unwrap())
+    };
+    assert_eq!(
+      get_derived_schema_node(&format!("{}/parameters/value-parameter", location)),
+      Some(serde_json::json!({ "type": "string" }))
+    );
+    assert_eq!(
+      get_derived_schema_node(&format!(
+        "{}/responses/echo-response/headers/value",
+        location
+      )),
+      Some(serde_json::json!({ "type": "string" }))
+    );
+    assert_eq!(
+      get_derived_schema_node(&form_schema_location),
+      Some(serde_json::json!({
+        "type": "object",
+        "properties": {
+          "file": { "type": "string", "format": "binary" },
+          "caption": { "type": "string" },
+        },
+        "required": ["caption"],
+      }))
+    );
+  }
 }
@@ -0,0 +1,57 @@
+use crate::error::Error;
+use crate::utils::NodeLocation;
+use std::rc;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticSeverity {
+  Error,
+  Warning,
+  Information,
+}
+
+/// A problem found while loading a document or making models out of it. The
+/// location points to the node that caused the problem.
+///
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Diagnostic {
+  pub location: NodeLocation,
+  pub message: String,
+  pub severity: DiagnosticSeverity,
+  /// the error that caused this diagnostic, if any
+  pub cause: Option<Error>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct DiagnosticContainer(rc::Rc<Diagnostic>);
+
+#[wasm_bindgen]
+impl DiagnosticContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "message")]
+  pub fn message(&self) -> String {
+    self.0.message.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "severity")]
+  pub fn severity(&self) -> DiagnosticSeverity {
+    self.0.severity
+  }
+
+  #[wasm_bindgen(getter, js_name = "cause")]
+  pub fn cause(&self) -> Option<Error> {
+    self.0.cause
+  }
+}
+
+impl From<rc::Rc<Diagnostic>> for DiagnosticContainer {
+  fn from(value: rc::Rc<Diagnostic>) -> Self {
+    Self(value)
+  }
+}
@@ -1,4 +1,4 @@
-use crate::models::{MethodParseError, StatusKindParseError};
+use crate::models::{MethodParseError, ParameterStyleParseError, StatusKindParseError};
 use crate::utils::ParseLocationError;
 use std::fmt::Display;
 use wasm_bindgen::prelude::*;
@@ -11,6 +11,10 @@ pub enum DocumentError {
   ParseLocationFailed,
   ParseMethodFailed,
   ParseStatusKindFailed,
+  ParseParameterStyleFailed,
+  ReferenceCycle,
+  MissingField,
+  ContextUnavailable,
 }
 
 impl std::error::Error for DocumentError {}
@@ -23,6 +27,10 @@ impl Display for DocumentError {
       Self::ParseLocationFailed => write!(f, "ParseLocationFailed"),
       Self::ParseMethodFailed => write!(f, "ParseMethodFailed"),
       Self::ParseStatusKindFailed => write!(f, "ParseStatusKindFailed"),
+      Self::ParseParameterStyleFailed => write!(f, "ParseParameterStyleFailed"),
+      Self::ReferenceCycle => write!(f, "ReferenceCycle"),
+      Self::MissingField => write!(f, "MissingField"),
+      Self::ContextUnavailable => write!(f, "ContextUnavailable"),
     }
   }
 }
@@ -44,3 +52,9 @@ impl From<StatusKindParseError> for DocumentError {
     Self::ParseStatusKindFailed
   }
 }
+
+impl From<ParameterStyleParseError> for DocumentError {
+  fn from(_value: ParameterStyleParseError) -> Self {
+    Self::ParseParameterStyleFailed
+  }
+}
@@ -0,0 +1,279 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Limits what documents are retrieved while loading, for instance when
+/// loading documents that come from an untrusted source. Documents that
+/// violate the policy are reported and never retrieved (or never followed).
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchPolicy {
+  /**
+   * only http(s) documents from these hosts (or their subdomains) are
+   * retrieved, if set
+   */
+  pub allowed_hosts: Option<Vec<String>>,
+  /**
+   * http(s) documents from these hosts (or their subdomains) are never
+   * retrieved, even if they are allowed
+   */
+  pub denied_hosts: Vec<String>,
+  /**
+   * if set, file documents are only retrieved from this directory or its
+   * descendants
+   */
+  pub file_root: Option<String>,
+  /**
+   * maximum size of a document in bytes, larger documents are not read any
+   * further than the maximum
+   */
+  pub maximum_document_size: Option<usize>,
+  /**
+   * maximum number of documents that are retrieved in one load
+   */
+  pub maximum_document_count: Option<usize>,
+  /**
+   * maximum number of references that are followed from the document that is
+   * loaded to a referenced document, the loaded document has depth 0
+   */
+  pub maximum_depth: Option<usize>,
+}
+
+impl FetchPolicy {
+  /// Checks if a document may be retrieved from the location (a fetch string),
+  /// returns the reason if it may not. Locations with a scheme other than
+  /// http(s) and file are never allowed here, they can only be retrieved by a
+  /// fetcher that is registered for them (see `has_custom_scheme`).
+  ///
+  pub fn check_location(&self, location: &str) -> Result<(), String> {
+    let location_lower = location.to_lowercase();
+
+    if location_lower.starts_with("http://") || location_lower.starts_with("https://") {
+      let host = get_host(&location_lower);
+      if self
+        .denied_hosts
+        .iter()
+        .any(|denied_host| match_host(&host, denied_host))
+      {
+        return Err(format!("host {} is denied", host));
+      }
+      if let Some(allowed_hosts) = &self.allowed_hosts {
+        if !allowed_hosts
+          .iter()
+          .any(|allowed_host| match_host(&host, allowed_host))
+        {
+          return Err(format!("host {} is not allowed", host));
+        }
+      }
+      return Ok(());
+    }
+
+    let path = if let Some(path) = location_lower
+      .starts_with("file://")
+      .then(|| &location["file://".len()..])
+    {
+      path
+    } else if let Some((scheme, _rest)) = location.split_once("://") {
+      return Err(format!("scheme {} is not supported", scheme));
+    } else {
+      location
+    };
+
+    if let Some(file_root) = &self.file_root {
+      let root_path = canonicalize_path(Path::new(file_root));
+      let path = canonicalize_path(Path::new(path));
+      if !path.starts_with(&root_path) {
+        return Err(format!(
+          "file {} is outside of {}",
+          path.display(),
+          root_path.display()
+        ));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// True if the location has a scheme other than http(s) and file, like in
+  /// memory documents.
+  ///
+  pub fn has_custom_scheme(location: &str) -> bool {
+    let location_lower = location.to_lowercase();
+    location_lower.contains("://")
+      && !location_lower.starts_with("http://")
+      && !location_lower.starts_with("https://")
+      && !location_lower.starts_with("file://")
+  }
+
+  pub fn check_document_count(&self, count: usize) -> Result<(), String> {
+    match self.maximum_document_count {
+      Some(maximum_document_count) if count > maximum_document_count => Err(format!(
+        "document count {} exceeds the maximum of {}",
+        count, maximum_document_count
+      )),
+      _ => Ok(()),
+    }
+  }
+
+  pub fn check_depth(&self, depth: usize) -> Result<(), String> {
+    match self.maximum_depth {
+      Some(maximum_depth) if depth > maximum_depth => Err(format!(
+        "reference depth {} exceeds the maximum of {}",
+        depth, maximum_depth
+      )),
+      _ => Ok(()),
+    }
+  }
+}
+
+/**
+the host of a (lower case) http(s) location, without user info and port
+*/
+fn get_host(location: &str) -> String {
+  let authority = location
+    .split_once("://")
+    .map(|(_scheme, rest)| rest)
+    .unwrap_or_default();
+  let authority = authority.split(['/', '?', '#']).next().unwrap_or_default();
+  let host = authority
+    .rsplit_once('@')
+    .map(|(_user_info, host)| host)
+    .unwrap_or(authority);
+
+  if let Some(host) = host.strip_prefix('[') {
+    // ipv6 address
+    return host.split(']').next().unwrap_or_default().to_owned();
+  }
+  host.split(':').next().unwrap_or_default().to_owned()
+}
+
+fn match_host(host: &str, pattern: &str) -> bool {
+  let pattern = pattern.to_lowercase();
+  host == pattern || host.ends_with(&format!(".{}", pattern))
+}
+
+/**
+make the path absolute and resolve symbolic links, . and .. so a path can not
+escape a directory via a link or via .. If the path does not exist, the part
+of it that exists is resolved and the rest is normalized. Without a file
+system (in wasm) the path is only normalized.
+*/
+fn canonicalize_path(path: &Path) -> PathBuf {
+  if let Ok(path) = std::fs::canonicalize(path) {
+    return path;
+  }
+
+  let path = normalize_path(path);
+  let mut names = Vec::new();
+  let mut existing_path = path.as_path();
+  while let (Some(parent_path), Some(name)) = (existing_path.parent(), existing_path.file_name()) {
+    names.push(name);
+    existing_path = parent_path;
+    if let Ok(existing_path) = std::fs::canonicalize(existing_path) {
+      return names
+        .iter()
+        .rev()
+        .fold(existing_path, |path, name| path.join(name));
+    }
+  }
+
+  path
+}
+
+/**
+make the path absolute and resolve . and .. without touching the file system
+*/
+fn normalize_path(path: &Path) -> PathBuf {
+  let path = if path.is_absolute() {
+    path.to_path_buf()
+  } else {
+    std::env::current_dir().unwrap_or_default().join(path)
+  };
+
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        normalized.pop();
+      }
+      component => normalized.push(component),
+    }
+  }
+  normalized
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_check_location() {
+    let policy = FetchPolicy {
+      allowed_hosts: Some(vec!["example.com".to_owned()]),
+      denied_hosts: vec!["private.example.com".to_owned()],
+      file_root: Some("/specifications".to_owned()),
+      ..Default::default()
+    };
+
+    assert!(policy
+      .check_location("https://example.com/api.yaml")
+      .is_ok());
+    assert!(policy
+      .check_location("https://user@api.example.com:8080/api.yaml")
+      .is_ok());
+    assert!(policy
+      .check_location("https://private.example.com/api.yaml")
+      .is_err());
+    assert!(policy
+      .check_location("https://example.org/api.yaml")
+      .is_err());
+    assert!(policy
+      .check_location("https://example.com.evil.org/api.yaml")
+      .is_err());
+
+    assert!(policy.check_location("/specifications/api.yaml").is_ok());
+    assert!(policy
+      .check_location("file:///specifications/models/user.yaml")
+      .is_ok());
+    assert!(policy
+      .check_location("/specifications/../etc/passwd")
+      .is_err());
+    assert!(policy.check_location("/etc/passwd").is_err());
+
+    assert!(policy
+      .check_location("memory://specifications/api.yaml")
+      .is_err());
+    assert!(FetchPolicy::has_custom_scheme(
+      "memory://specifications/api.yaml"
+    ));
+    assert!(!FetchPolicy::has_custom_scheme("/specifications/api.yaml"));
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_check_location_link() {
+    let directory =
+      std::env::temp_dir().join(format!("skiffa-fetch-policy-{}", std::process::id()));
+    let root_directory = directory.join("specifications");
+    std::fs::create_dir_all(&root_directory).unwrap();
+    std::fs::write(directory.join("secret.yaml"), "secret: true").unwrap();
+    std::fs::write(root_directory.join("api.yaml"), "openapi: 3.0.0").unwrap();
+    std::os::unix::fs::symlink(
+      directory.join("secret.yaml"),
+      root_directory.join("link.yaml"),
+    )
+    .unwrap();
+
+    let policy = FetchPolicy {
+      file_root: Some(root_directory.to_str().unwrap().to_owned()),
+      ..Default::default()
+    };
+    let check_location =
+      |name: &str| policy.check_location(root_directory.join(name).to_str().unwrap());
+
+    assert!(check_location("api.yaml").is_ok());
+    assert!(check_location("missing.yaml").is_ok());
+    assert!(check_location("link.yaml").is_err());
+
+    let _ = std::fs::remove_dir_all(&directory);
+  }
+}
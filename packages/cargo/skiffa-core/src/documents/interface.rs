@@ -1,4 +1,4 @@
-use super::DocumentError;
+use super::{Diagnostic, DocumentError};
 use crate::models;
 use crate::utils::NodeLocation;
 use std::rc;
@@ -7,8 +7,13 @@ pub trait DocumentInterface {
   fn get_default_schema_id(&self) -> String;
   fn get_document_location(&self) -> NodeLocation;
   fn get_schema_locations(&self) -> Result<Vec<NodeLocation>, DocumentError>;
+  /// Schemas that are not a node of the document, but are derived from one
+  /// (like the schema of a swagger 2 query parameter), with their location.
+  fn get_derived_schemas(&self) -> Result<Vec<(NodeLocation, serde_json::Value)>, DocumentError>;
   fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError>;
   fn get_api_model(&self) -> Result<rc::Rc<models::Api>, DocumentError>;
+  /// Validates the document against the meta-schema of the document type
+  fn validate(&self) -> Result<Vec<Diagnostic>, DocumentError>;
 }
 
 pub struct DocumentConfiguration {
@@ -0,0 +1,50 @@
+use crate::error::Error;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+/// Pins the content of every retrieved document by its location, so a
+/// change in a (remote) document is noticed instead of silently changing the
+/// generated code.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Lockfile {
+  /**
+   * content hashes by the location (fetch string) of the documents
+   */
+  pub documents: BTreeMap<String, String>,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockfileMode {
+  /// Documents that are not pinned, or that do not match their pin, fail to
+  /// load
+  #[default]
+  Verify,
+  /// The pins are rewritten from the retrieved documents, pins of documents
+  /// that are not retrieved are dropped
+  Update,
+}
+
+impl Lockfile {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn from_json(data: &str) -> Result<Self, Error> {
+    serde_json::from_str(data).map_err(|_error| Error::SerializationError)
+  }
+
+  pub fn to_json(&self) -> Result<String, Error> {
+    serde_json::to_string_pretty(self).map_err(|_error| Error::SerializationError)
+  }
+
+  /// The hash of the content of a document, like `sha256-<hex digest>`
+  ///
+  pub fn make_content_hash(data: &str) -> String {
+    let hash = Sha256::digest(data.as_bytes());
+    let hex: String = hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256-{}", hex)
+  }
+}
@@ -1,4 +1,4 @@
 mod document;
-mod nodes;
+pub(super) mod nodes;
 
 pub use document::*;
@@ -1,16 +1,28 @@
 use super::nodes;
 use crate::models::{AuthenticationRequirement, AuthenticationRequirementGroup, StatusKind};
-use crate::utils::NodeLocation;
+use crate::utils::{format_reference_cycle, resolve_server_url, NodeLocation};
 use crate::{
-  documents::{DocumentContext, DocumentError, DocumentInterface},
+  documents::{
+    make_meta_schema_validator, validate_node, Diagnostic, DiagnosticSeverity, DocumentContext,
+    DocumentError, DocumentInterface,
+  },
   models,
 };
+use once_cell::sync::Lazy;
 use std::collections::{BTreeMap, BTreeSet};
 use std::{iter, rc};
 
+static META_SCHEMA_VALIDATOR: Lazy<jsonschema::Validator> =
+  Lazy::new(|| make_meta_schema_validator(include_str!("schema.json")));
+
 pub struct Document {
   context: rc::Weak<DocumentContext>,
   retrieval_location: NodeLocation,
+  /**
+   * a reference may override the summary and description of the referenced
+   * node, this was introduced in 3.1
+   */
+  override_summary_and_description: bool,
 }
 
 impl Document {
@@ -18,6 +30,21 @@ impl Document {
     Self {
       context,
       retrieval_location,
+      override_summary_and_description: false,
+    }
+  }
+
+  /// The 3.1 document builds its models with this document, the only
+  /// difference is in how references are followed.
+  ///
+  pub(in crate::documents) fn new_with_summary_and_description_overrides(
+    context: rc::Weak<DocumentContext>,
+    retrieval_location: NodeLocation,
+  ) -> Self {
+    Self {
+      context,
+      retrieval_location,
+      override_summary_and_description: true,
     }
   }
 }
@@ -39,13 +66,13 @@ impl Document {
           None
         }
       })
-      .map(|(_location, reference)| {
-        let reference_location: NodeLocation = reference.parse()?;
-        Ok(reference_location)
+      .filter_map(|(_location, reference)| {
+        let reference = reference.reference()?;
+        Some(reference.parse().map_err(Into::into))
       })
   }
 
-  fn get_sub_locations_from_node_entries<N, SR>(
+  pub(in crate::documents) fn get_sub_locations_from_node_entries<N, SR>(
     location: NodeLocation,
     entries: impl Iterator<Item = (Vec<String>, N)>,
     selector: impl Fn(NodeLocation, N) -> SR,
@@ -62,11 +89,25 @@ impl Document {
       .flat_map(move |(location, node)| (selector)(location, node))
   }
 
-  fn get_node<T>(&self, location: &NodeLocation) -> Result<T, DocumentError>
+  /**
+  the context owns the document, so the context should always be available
+  while the document is used
+  */
+  pub(in crate::documents) fn get_context(&self) -> Result<rc::Rc<DocumentContext>, DocumentError> {
+    self
+      .context
+      .upgrade()
+      .ok_or(DocumentError::ContextUnavailable)
+  }
+
+  pub(in crate::documents) fn get_node<T>(
+    &self,
+    location: &NodeLocation,
+  ) -> Result<T, DocumentError>
   where
     T: From<serde_json::Value>,
   {
-    let context = self.context.upgrade().unwrap();
+    let context = self.get_context()?;
     let node = context
       .get_node(location)
       .ok_or(DocumentError::NodeNotFound)?;
@@ -82,20 +123,36 @@ impl Document {
   where
     T: From<serde_json::Value>,
   {
-    match node {
-      nodes::NodeOrReference::Reference(reference) => {
-        let reference_location: NodeLocation = reference.parse()?;
-        let context = self.context.upgrade().unwrap();
-        let location = location.join(&reference_location);
-        let node = context
-          .get_node(&location)
-          .ok_or(DocumentError::NodeNotFound)?
-          .clone();
-        let node = node.into();
-        Ok((location, node))
-      }
-      nodes::NodeOrReference::Node(node) => Ok((location.clone(), node)),
-    }
+    let context = self.get_context()?;
+    dereference_node(
+      &context,
+      location,
+      node,
+      self.override_summary_and_description,
+    )
+  }
+
+  pub(in crate::documents) fn resolve_path(
+    &self,
+    location: &NodeLocation,
+    node: nodes::Path,
+  ) -> Result<Vec<(NodeLocation, nodes::Path)>, DocumentError> {
+    let context = self.get_context()?;
+    resolve_path_node(&context, location, node, nodes::Path::reference)
+  }
+
+  fn merge_parameter_nodes(
+    &self,
+    parameter_nodes: &[(NodeLocation, nodes::RequestParameter)],
+    override_parameter_nodes: Vec<(NodeLocation, nodes::RequestParameter)>,
+  ) -> Result<Vec<(NodeLocation, nodes::RequestParameter)>, DocumentError> {
+    let context = self.get_context()?;
+    merge_parameter_nodes(
+      &context,
+      parameter_nodes,
+      override_parameter_nodes,
+      |node| (node.r#in(), node.name()),
+    )
   }
 }
 
@@ -115,6 +172,19 @@ impl DocumentInterface for Document {
     self.make_api_model(api_location, api_node).map(rc::Rc::new)
   }
 
+  fn validate(&self) -> Result<Vec<Diagnostic>, DocumentError> {
+    let context = self.get_context()?;
+    let api_node = context
+      .get_node(&self.retrieval_location)
+      .ok_or(DocumentError::NodeNotFound)?;
+
+    Ok(validate_node(
+      &META_SCHEMA_VALIDATOR,
+      &self.retrieval_location,
+      &api_node,
+    ))
+  }
+
   fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
     let api_location = self.retrieval_location.clone();
     let api_node = self.get_node(&api_location)?;
@@ -132,47 +202,83 @@ impl DocumentInterface for Document {
       .get_schema_locations_from_api(api_location, api_node)
       .collect()
   }
+
+  fn get_derived_schemas(&self) -> Result<Vec<(NodeLocation, serde_json::Value)>, DocumentError> {
+    // every schema is a node of the document
+    Ok(Vec::new())
+  }
 }
 
 impl Document {
-  fn make_api_model(
+  pub(in crate::documents) fn make_api_model(
     &self,
     api_location: NodeLocation,
     api_node: nodes::Api,
   ) -> Result<models::Api, DocumentError> {
+    /*
+    if there are no servers, the default is a single server with url /
+    */
+    let default_servers = [rc::Rc::new(models::Server {
+      location: api_location.clone(),
+      url: resolve_server_url(&api_location, "/"),
+      description: None,
+      variables: Vec::new(),
+    })];
+    let servers = self.make_server_models(&api_location, api_node.servers(), &default_servers)?;
+
+    let context = self.get_context()?;
+
+    /*
+    paths and authentication models that fail are reported and skipped, so
+    one error does not prevent the rest of the api from being used
+    */
     let paths = api_node
       .paths()
       .into_iter()
       .flatten()
       .enumerate()
-      .map(|(index, (pointer, node))| {
+      .filter_map(|(index, (pointer, node))| {
         let pattern = pointer.last().unwrap().clone();
         let id = index + 1;
         let location = api_location.push_pointer(pointer);
-        let (location, node) = self.dereference(&location, node)?;
-        self
-          .make_path_model(api_node.clone(), location, node, id, pattern)
+        let result = self.resolve_path(&location, node).and_then(|layers| {
+          self.make_path_model(
+            api_node.clone(),
+            &servers,
+            location.clone(),
+            layers,
+            id,
+            pattern,
+          )
+        });
+        context
+          .report_result(&location, "could not make the path model", result)
           .map(rc::Rc::new)
       })
-      .collect::<Result<_, DocumentError>>()?;
+      .collect();
 
     let authentication = api_node
       .security_schemes()
       .into_iter()
       .flatten()
-      .map(|(pointer, node)| {
+      .filter_map(|(pointer, node)| {
         let name = pointer.last().unwrap().clone();
         let location = api_location.push_pointer(pointer);
-        let (location, node) = self.dereference(&location, node)?;
-        self
-          .make_authentication_model(location, node, name)
+        let result = self
+          .dereference(&location, node)
+          .and_then(|(location, node)| self.make_authentication_model(location, node, name));
+        context
+          .report_result(&location, "could not make the authentication model", result)
           .map(rc::Rc::new)
       })
-      .collect::<Result<_, DocumentError>>()?;
+      .collect();
 
     Ok(models::Api {
       location: api_location.clone(),
+      servers,
       paths,
+      // webhooks were introduced in openapi 3.1, the 3.1 document adds them
+      webhooks: Vec::new(),
       authentication,
     })
   }
@@ -180,49 +286,125 @@ impl Document {
   fn make_path_model(
     &self,
     api_node: nodes::Api,
+    api_servers: &[rc::Rc<models::Server>],
     path_location: NodeLocation,
-    path_node: nodes::Path,
+    path_layers: Vec<(NodeLocation, nodes::Path)>,
     id: usize,
     pattern: String,
   ) -> Result<models::Path, DocumentError> {
-    let operations = path_node
-      .operations()
-      .into_iter()
-      .flatten()
-      .map(|(pointer, node)| {
-        let method = pointer.last().unwrap().as_str().parse()?;
-        let location = path_location.push_pointer(pointer);
-        self
-          .make_operation_model(
-            api_node.clone(),
-            path_location.clone(),
-            path_node.clone(),
-            location,
-            node,
-            method,
-          )
-          .map(rc::Rc::new)
-      })
-      .collect::<Result<_, DocumentError>>()?;
+    let servers = self.make_path_server_models(&path_layers, api_servers)?;
+    let operations = self.make_operation_models(api_node, &servers, &path_layers)?;
 
     Ok(models::Path {
       id,
       location: path_location.clone(),
       pattern,
+      servers,
       operations,
     })
   }
 
+  /// The servers of the last path item layer that has servers, or the servers
+  /// of the api.
+  ///
+  pub(in crate::documents) fn make_path_server_models(
+    &self,
+    path_layers: &[(NodeLocation, nodes::Path)],
+    api_servers: &[rc::Rc<models::Server>],
+  ) -> Result<Vec<rc::Rc<models::Server>>, DocumentError> {
+    path_layers
+      .iter()
+      .try_fold(api_servers.to_vec(), |servers, (location, node)| {
+        self.make_server_models(location, node.servers(), &servers)
+      })
+  }
+
+  pub(in crate::documents) fn make_operation_models(
+    &self,
+    api_node: nodes::Api,
+    path_servers: &[rc::Rc<models::Server>],
+    path_layers: &[(NodeLocation, nodes::Path)],
+  ) -> Result<Vec<rc::Rc<models::Operation>>, DocumentError> {
+    /*
+    parameters on the referencing path item override the parameters on the
+    referenced path item
+    */
+    let path_parameter_nodes = path_layers
+      .iter()
+      .map(|(location, node)| {
+        node
+          .request_parameters()
+          .into_iter()
+          .flatten()
+          .map(|(pointer, node)| {
+            let location = location.push_pointer(pointer);
+            self.dereference(&location, node)
+          })
+          .collect::<Result<Vec<_>, DocumentError>>()
+      })
+      .try_fold(Vec::new(), |parameter_nodes, layer_parameter_nodes| {
+        layer_parameter_nodes.and_then(|layer_parameter_nodes| {
+          self.merge_parameter_nodes(&parameter_nodes, layer_parameter_nodes)
+        })
+      })?;
+
+    /*
+    operations on the referencing path item override the operations on the
+    referenced path item
+    */
+    let operation_nodes: BTreeMap<_, _> = path_layers
+      .iter()
+      .flat_map(|(location, node)| {
+        node
+          .operations()
+          .into_iter()
+          .flatten()
+          .map(move |(pointer, node)| {
+            let method = pointer.last().unwrap().clone();
+            (method, (location.push_pointer(pointer), node))
+          })
+      })
+      .collect();
+
+    let context = self.get_context()?;
+    let operations = operation_nodes
+      .into_iter()
+      .filter_map(|(method, (location, node))| {
+        let result = method
+          .as_str()
+          .parse()
+          .map_err(DocumentError::from)
+          .and_then(|method| {
+            self.make_operation_model(
+              api_node.clone(),
+              path_servers,
+              &path_parameter_nodes,
+              location.clone(),
+              node,
+              method,
+            )
+          });
+        context
+          .report_result(&location, "could not make the operation model", result)
+          .map(rc::Rc::new)
+      })
+      .collect();
+
+    Ok(operations)
+  }
+
   fn make_operation_model(
     &self,
     api_node: nodes::Api,
-    path_location: NodeLocation,
-    path_node: nodes::Path,
+    path_servers: &[rc::Rc<models::Server>],
+    path_parameter_nodes: &[(NodeLocation, nodes::RequestParameter)],
     operation_location: NodeLocation,
     operation_node: nodes::Operation,
     method: models::Method,
   ) -> Result<models::Operation, DocumentError> {
     let mut status_codes_available = (100..600).collect();
+    let servers =
+      self.make_server_models(&operation_location, operation_node.servers(), path_servers)?;
     let authentication_requirements = None
       .or_else(|| operation_node.security())
       .or_else(|| api_node.security())
@@ -232,28 +414,21 @@ impl Document {
       .map(rc::Rc::new)
       .collect();
 
-    let all_parameter_nodes = iter::empty()
-      .chain(
-        path_node
-          .request_parameters()
-          .into_iter()
-          .flatten()
-          .map(|(pointer, node)| {
-            let location = path_location.push_pointer(pointer);
-            self.dereference(&location, node)
-          }),
-      )
-      .chain(
-        operation_node
-          .request_parameters()
-          .into_iter()
-          .flatten()
-          .map(|(pointer, node)| {
-            let location = operation_location.push_pointer(pointer);
-            self.dereference(&location, node)
-          }),
-      )
+    /*
+    operation parameters override path parameters with the same name and
+    location
+    */
+    let operation_parameter_nodes = operation_node
+      .request_parameters()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let location = operation_location.push_pointer(pointer);
+        self.dereference(&location, node)
+      })
       .collect::<Result<Vec<_>, DocumentError>>()?;
+    let all_parameter_nodes =
+      self.merge_parameter_nodes(path_parameter_nodes, operation_parameter_nodes)?;
 
     let cookie_parameters = all_parameter_nodes
       .iter()
@@ -315,16 +490,28 @@ impl Document {
       })
       .collect::<Result<Vec<_>, DocumentError>>()?;
 
-    let bodies = operation_node
-      .bodies()
-      .into_iter()
-      .flatten()
+    let request_body = operation_node
+      .request_body()
       .map(|(pointer, node)| {
-        let content_type = pointer.last().unwrap().clone();
         let location = operation_location.push_pointer(pointer);
-        self
-          .make_body_model(location, node.clone(), content_type)
-          .map(rc::Rc::new)
+        self.dereference(&location, node)
+      })
+      .transpose()?;
+
+    let bodies = request_body
+      .iter()
+      .flat_map(|(request_body_location, request_body_node)| {
+        request_body_node
+          .bodies()
+          .into_iter()
+          .flatten()
+          .map(|(pointer, node)| {
+            let content_type = pointer.last().unwrap().clone();
+            let location = request_body_location.push_pointer(pointer);
+            self
+              .make_body_model(location, node.clone(), content_type)
+              .map(rc::Rc::new)
+          })
       })
       .collect::<Result<Vec<_>, DocumentError>>()?;
 
@@ -360,11 +547,25 @@ impl Document {
 
     Ok(models::Operation {
       location: operation_location.clone(),
-      name: operation_node.name().map(Into::into).unwrap(),
+      /*
+      operations without an operationId are named by the context when the api
+      model is complete, so names can be checked for collisions
+      */
+      name: operation_node.name().map(Into::into).unwrap_or_default(),
+      operation_id: operation_node.name().map(Into::into),
       summary: operation_node.summary().map(Into::into),
       description: operation_node.description().map(Into::into),
       deprecated: operation_node.deprecated().unwrap_or(false),
+      servers,
       method,
+      body_required: request_body
+        .as_ref()
+        .and_then(|(_location, node)| node.required())
+        .unwrap_or(false),
+      body_description: request_body
+        .as_ref()
+        .and_then(|(_location, node)| node.description())
+        .map(Into::into),
       authentication_requirements,
       cookie_parameters,
       header_parameters,
@@ -375,6 +576,79 @@ impl Document {
     })
   }
 
+  fn make_server_models(
+    &self,
+    location: &NodeLocation,
+    server_nodes: Option<Vec<(Vec<String>, nodes::Server)>>,
+    parent_servers: &[rc::Rc<models::Server>],
+  ) -> Result<Vec<rc::Rc<models::Server>>, DocumentError> {
+    let servers = server_nodes
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let location = location.push_pointer(pointer);
+        self.make_server_model(location, node).map(rc::Rc::new)
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    /*
+    servers are inherited from the parent if there are none
+    */
+    if servers.is_empty() {
+      Ok(parent_servers.to_vec())
+    } else {
+      Ok(servers)
+    }
+  }
+
+  fn make_server_model(
+    &self,
+    server_location: NodeLocation,
+    server_node: nodes::Server,
+  ) -> Result<models::Server, DocumentError> {
+    let variables = server_node
+      .variables()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let name = pointer.last().unwrap().clone();
+        let location = server_location.push_pointer(pointer);
+        self
+          .make_server_variable_model(location, node, name)
+          .map(rc::Rc::new)
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    Ok(models::Server {
+      location: server_location.clone(),
+      /*
+      relative urls are relative to the document that defines the server, that
+      is not the retrieved document for path items in another document
+      */
+      url: resolve_server_url(&server_location, server_node.url().unwrap_or_default()),
+      description: server_node.description().map(Into::into),
+      variables,
+    })
+  }
+
+  fn make_server_variable_model(
+    &self,
+    server_variable_location: NodeLocation,
+    server_variable_node: nodes::ServerVariable,
+    name: String,
+  ) -> Result<models::ServerVariable, DocumentError> {
+    Ok(models::ServerVariable {
+      location: server_variable_location.clone(),
+      name,
+      default: server_variable_node
+        .default()
+        .map(Into::into)
+        .unwrap_or_default(),
+      r#enum: server_variable_node.r#enum().unwrap_or_default(),
+      description: server_variable_node.description().map(Into::into),
+    })
+  }
+
   fn make_operation_result_model(
     &self,
     operation_result_location: NodeLocation,
@@ -450,10 +724,29 @@ impl Document {
       .schema_pointer()
       .map(|pointer| parameter_location.push_pointer(pointer));
 
+    let r#in = parameter_node.r#in().unwrap_or_default();
+    let style = match parameter_node.style() {
+      Some(style) => style.parse()?,
+      None => models::ParameterStyle::default_for(r#in),
+    };
+
     Ok(models::Parameter {
       location: parameter_location.clone(),
-      name: parameter_node.name().map(Into::into).unwrap(),
-      required: parameter_node.required().unwrap_or(false),
+      name: parameter_node
+        .name()
+        .map(Into::into)
+        .ok_or(DocumentError::MissingField)?,
+      description: parameter_node.description().map(Into::into),
+      // path parameters are always required
+      required: r#in == "path" || parameter_node.required().unwrap_or(false),
+      deprecated: parameter_node.deprecated().unwrap_or(false),
+      style,
+      explode: parameter_node
+        .explode()
+        .unwrap_or_else(|| style.default_explode()),
+      allow_reserved: parameter_node.allow_reserved().unwrap_or(false),
+      allow_empty_value: parameter_node.allow_empty_value().unwrap_or(false),
+      content_type: parameter_node.content_type().map(Into::into),
       schema_id,
     })
   }
@@ -468,10 +761,25 @@ impl Document {
       .schema_pointer()
       .map(|pointer| header_location.push_pointer(pointer));
 
+    // headers always have the simple style
+    let style = match header_node.style() {
+      Some(style) => style.parse()?,
+      None => models::ParameterStyle::Simple,
+    };
+
     Ok(models::Parameter {
       location: header_location.clone(),
       name,
+      description: header_node.description().map(Into::into),
       required: header_node.required().unwrap_or(false),
+      deprecated: header_node.deprecated().unwrap_or(false),
+      style,
+      explode: header_node
+        .explode()
+        .unwrap_or_else(|| style.default_explode()),
+      allow_reserved: false,
+      allow_empty_value: false,
+      content_type: header_node.content_type().map(Into::into),
       schema_id,
     })
   }
@@ -522,33 +830,63 @@ impl Document {
 }
 
 impl Document {
-  fn get_referenced_locations_from_api(
+  pub(in crate::documents) fn get_referenced_locations_from_api(
     &self,
     location: NodeLocation,
     node: nodes::Api,
   ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    /*
+    components may reference nodes in other documents too, and responses
+    refer to headers
+    */
     iter::empty()
       .chain(Self::get_referenced_locations_from_reference_entries(
         location.clone(),
-        node.paths().into_iter().flatten(),
+        node.parameter_components().into_iter().flatten(),
+      ))
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.header_components().into_iter().flatten(),
+      ))
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.request_body_components().into_iter().flatten(),
+      ))
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.response_components().into_iter().flatten(),
       ))
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
         node
-          .paths()
+          .response_components()
           .into_iter()
           .flatten()
           .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_referenced_locations_from_operation_result(location, node),
+      ))
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.security_schemes().into_iter().flatten(),
+      ))
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.paths().into_iter().flatten(),
         |location, node| self.get_referenced_locations_from_path(location, node),
       ))
   }
 
-  fn get_referenced_locations_from_path(
+  pub(in crate::documents) fn get_referenced_locations_from_path(
     &self,
     location: NodeLocation,
     node: nodes::Path,
   ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    let reference_location = node
+      .reference()
+      .map(|reference| reference.parse().map_err(Into::into));
+
     iter::empty()
+      .chain(reference_location)
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
         node.operations().into_iter().flatten(),
@@ -583,6 +921,10 @@ impl Document {
         location.clone(),
         node.request_parameters().into_iter().flatten(),
       ))
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.request_body().into_iter(),
+      ))
   }
 
   fn get_referenced_locations_from_operation_result(
@@ -598,7 +940,7 @@ impl Document {
 }
 
 impl Document {
-  fn get_schema_locations_from_api(
+  pub(in crate::documents) fn get_schema_locations_from_api(
     &self,
     location: NodeLocation,
     node: nodes::Api,
@@ -614,31 +956,48 @@ impl Document {
       })
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
-        node.parameter_components().into_iter().flatten(),
+        node
+          .parameter_components()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
         |location, node| self.get_schema_locations_from_request_parameter(location, node),
       ))
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
-        node.header_components().into_iter().flatten(),
+        node
+          .header_components()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
         |location, node| self.get_schema_locations_from_response_header(location, node),
       ))
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
-        node.response_components().into_iter().flatten(),
-        |location, node| self.get_schema_locations_from_operation_result(location, node),
+        node
+          .request_body_components()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_request_body(location, node),
       ))
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
         node
-          .paths()
+          .response_components()
           .into_iter()
           .flatten()
           .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_operation_result(location, node),
+      ))
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.paths().into_iter().flatten(),
         |location, node| self.get_schema_locations_from_path(location, node),
       ))
   }
 
-  fn get_schema_locations_from_path(
+  pub(in crate::documents) fn get_schema_locations_from_path(
     &self,
     location: NodeLocation,
     node: nodes::Path,
@@ -677,8 +1036,16 @@ impl Document {
       ))
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
-        node.bodies().into_iter().flatten(),
-        |location, node| self.get_schema_locations_from_body(location, node),
+        node
+          .request_body()
+          .into_iter()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| {
+          self
+            .get_schema_locations_from_request_body(location, node)
+            .collect::<Vec<_>>()
+            .into_iter()
+        },
       ))
       .chain(Self::get_sub_locations_from_node_entries(
         location.clone(),
@@ -696,6 +1063,18 @@ impl Document {
       ))
   }
 
+  fn get_schema_locations_from_request_body(
+    &self,
+    location: NodeLocation,
+    node: nodes::RequestBody,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    Self::get_sub_locations_from_node_entries(
+      location,
+      node.bodies().into_iter().flatten(),
+      |location, node| self.get_schema_locations_from_body(location, node),
+    )
+  }
+
   fn get_schema_locations_from_operation_result(
     &self,
     location: NodeLocation,
@@ -764,3 +1143,189 @@ impl Document {
       .map(Ok)
   }
 }
+
+/// Follows a reference, and the references it leads to, until a node is
+/// found. The locations that are visited are remembered so a cycle of
+/// references is detected and reported (with every location in the cycle)
+/// instead of being followed forever. This is shared by the 2.0, 3.0 and 3.1
+/// documents, in 3.1 a reference may override the summary and description of
+/// the referenced node.
+///
+pub(in crate::documents) fn dereference_node<T>(
+  context: &DocumentContext,
+  location: &NodeLocation,
+  node: nodes::NodeOrReference<T>,
+  override_summary_and_description: bool,
+) -> Result<(NodeLocation, T), DocumentError>
+where
+  T: From<serde_json::Value>,
+{
+  let mut visited_locations = vec![location.clone()];
+  let mut node = node;
+
+  /*
+  the reference that is closest to the referencing node takes precedence
+  */
+  let mut summary = None;
+  let mut description = None;
+
+  loop {
+    let reference = match node {
+      nodes::NodeOrReference::Reference(reference) => reference,
+      nodes::NodeOrReference::Node(node) => {
+        let location = visited_locations.pop().unwrap();
+        return Ok((location, node));
+      }
+    };
+
+    let referencing_location = visited_locations.last().unwrap();
+    let reference_location: NodeLocation = reference
+      .reference()
+      .ok_or(DocumentError::NodeNotFound)?
+      .parse()?;
+    let target_location = referencing_location.join(&reference_location);
+    if let Some(index) = visited_locations
+      .iter()
+      .position(|visited_location| *visited_location == target_location)
+    {
+      context.report_error(
+        location,
+        &format!(
+          "reference cycle {}",
+          format_reference_cycle(&visited_locations[index..])
+        ),
+        DocumentError::ReferenceCycle,
+      );
+      return Err(DocumentError::ReferenceCycle);
+    }
+    let Some(mut target_node) = context.get_node(&target_location) else {
+      context.report_error(
+        referencing_location,
+        &format!("could not resolve reference to {}", target_location),
+        DocumentError::NodeNotFound,
+      );
+      return Err(DocumentError::NodeNotFound);
+    };
+
+    if override_summary_and_description {
+      summary = summary.or(reference.summary().map(ToOwned::to_owned));
+      description = description.or(reference.description().map(ToOwned::to_owned));
+    }
+    if let Some(object) = target_node.as_object_mut() {
+      if let Some(summary) = &summary {
+        object.insert("summary".to_owned(), summary.clone().into());
+      }
+      if let Some(description) = &description {
+        object.insert("description".to_owned(), description.clone().into());
+      }
+    }
+
+    visited_locations.push(target_location);
+    node = target_node.into();
+  }
+}
+
+/// A path item may reference another path item and still have fields of its
+/// own. This function returns the referenced path items (if any) first and
+/// then the path item itself, so fields of the latter take precedence.
+///
+pub(in crate::documents) fn resolve_path_node<P>(
+  context: &DocumentContext,
+  location: &NodeLocation,
+  node: P,
+  get_reference: impl Fn(&P) -> Option<&str>,
+) -> Result<Vec<(NodeLocation, P)>, DocumentError>
+where
+  P: From<serde_json::Value>,
+{
+  let mut layers = vec![(location.clone(), node)];
+
+  /*
+  the referenced path item may reference a path item itself, so references
+  are followed until a path item without a reference is found
+  */
+  while let Some((referencing_location, reference)) = layers
+    .last()
+    .and_then(|(location, node)| Some((location.clone(), get_reference(node)?.to_owned())))
+  {
+    let reference_location: NodeLocation = reference.parse()?;
+    let reference_location = referencing_location.join(&reference_location);
+    if let Some(index) = layers
+      .iter()
+      .position(|(location, _node)| *location == reference_location)
+    {
+      let locations: Vec<_> = layers[index..]
+        .iter()
+        .map(|(location, _node)| location.clone())
+        .collect();
+      context.report_error(
+        location,
+        &format!("reference cycle {}", format_reference_cycle(&locations)),
+        DocumentError::ReferenceCycle,
+      );
+      return Err(DocumentError::ReferenceCycle);
+    }
+    let reference_node = context
+      .get_node(&reference_location)
+      .ok_or(DocumentError::NodeNotFound)?
+      .into();
+    layers.push((reference_location, reference_node));
+  }
+
+  layers.reverse();
+
+  Ok(layers)
+}
+
+/// Merges parameters with the parameters that override them. Parameters are
+/// identified by their name and location (in), as returned by `get_in_and_name`.
+/// Header names are case insensitive, so they are compared in lower case.
+/// A parameter that is defined more than once in the same list is reported,
+/// the first definition wins. The 2.0 document uses its own parameter nodes.
+///
+pub(in crate::documents) fn merge_parameter_nodes<N>(
+  context: &DocumentContext,
+  parameter_nodes: &[(NodeLocation, N)],
+  override_parameter_nodes: Vec<(NodeLocation, N)>,
+  get_in_and_name: impl Fn(&N) -> (Option<&str>, Option<&str>),
+) -> Result<Vec<(NodeLocation, N)>, DocumentError>
+where
+  N: Clone,
+{
+  let get_key = |node: &N| {
+    let (r#in, name) = get_in_and_name(node);
+    let r#in = r#in.unwrap_or_default();
+    let name = name.unwrap_or_default();
+    if r#in == "header" {
+      (r#in.to_owned(), name.to_lowercase())
+    } else {
+      (r#in.to_owned(), name.to_owned())
+    }
+  };
+
+  let mut parameter_nodes = parameter_nodes.to_vec();
+  let mut override_keys = BTreeSet::new();
+  for (location, node) in override_parameter_nodes {
+    let key = get_key(&node);
+    if !override_keys.insert(key.clone()) {
+      let (r#in, name) = key;
+      context.report_diagnostic(Diagnostic {
+        location,
+        message: format!("{} parameter {} is defined more than once", r#in, name),
+        severity: DiagnosticSeverity::Warning,
+        cause: None,
+      });
+      continue;
+    }
+
+    match parameter_nodes
+      .iter()
+      .position(|(_location, node)| get_key(node) == key)
+    {
+      Some(index) => parameter_nodes[index] = (location, node),
+      None => parameter_nodes.push((location, node)),
+    }
+  }
+
+  Ok(parameter_nodes)
+}
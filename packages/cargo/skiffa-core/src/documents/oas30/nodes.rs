@@ -5,9 +5,12 @@ mod operation;
 mod operation_result;
 mod path;
 mod reference;
+mod request_body;
 mod request_parameter;
 mod response_header;
 mod security_scheme;
+mod server;
+mod server_variable;
 
 pub use api::*;
 pub use body::*;
@@ -16,6 +19,9 @@ pub use operation::*;
 pub use operation_result::*;
 pub use path::*;
 pub use reference::*;
+pub use request_body::*;
 pub use request_parameter::*;
 pub use response_header::*;
 pub use security_scheme::*;
+pub use server::*;
+pub use server_variable::*;
@@ -5,7 +5,27 @@ use std::collections::{BTreeMap, BTreeSet};
 pub struct Api(serde_json::Value);
 
 impl Api {
-  pub fn paths(&self) -> Option<BTreeMap<Vec<String>, NodeOrReference<Path>>> {
+  pub fn servers(&self) -> Option<Vec<(Vec<String>, Server)>> {
+    let member = "servers";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_array()?
+        .iter()
+        .enumerate()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), key.to_string()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn paths(&self) -> Option<BTreeMap<Vec<String>, Path>> {
     let member = "paths";
     Some(
       self
@@ -93,7 +113,9 @@ impl Api {
     )
   }
 
-  pub fn parameter_components(&self) -> Option<BTreeMap<Vec<String>, RequestParameter>> {
+  pub fn parameter_components(
+    &self,
+  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<RequestParameter>>> {
     let member = "components";
     let member_1 = "parameters";
     Some(
@@ -115,7 +137,9 @@ impl Api {
     )
   }
 
-  pub fn header_components(&self) -> Option<BTreeMap<Vec<String>, ResponseHeader>> {
+  pub fn header_components(
+    &self,
+  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<ResponseHeader>>> {
     let member = "components";
     let member_1 = "headers";
     Some(
@@ -137,7 +161,33 @@ impl Api {
     )
   }
 
-  pub fn response_components(&self) -> Option<BTreeMap<Vec<String>, OperationResult>> {
+  pub fn request_body_components(
+    &self,
+  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<RequestBody>>> {
+    let member = "components";
+    let member_1 = "requestBodies";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn response_components(
+    &self,
+  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<OperationResult>>> {
     let member = "components";
     let member_1 = "responses";
     Some(
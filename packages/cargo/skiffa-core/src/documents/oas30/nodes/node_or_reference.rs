@@ -6,7 +6,7 @@ where
   T: From<serde_json::Value>,
 {
   Node(T),
-  Reference(String),
+  Reference(Reference),
 }
 
 impl<T> NodeOrReference<T>
@@ -27,8 +27,8 @@ where
 {
   fn from(value: serde_json::Value) -> Self {
     let reference_node: Reference = value.clone().into();
-    if let Some(reference) = reference_node.reference() {
-      return NodeOrReference::Reference(reference.to_owned());
+    if reference_node.reference().is_some() {
+      return NodeOrReference::Reference(reference_node);
     }
     NodeOrReference::Node(value.into())
   }
@@ -22,10 +22,8 @@ impl Operation {
     self.0.as_object()?.get("deprecated")?.as_bool()
   }
 
-  pub fn request_parameters(
-    &self,
-  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<RequestParameter>>> {
-    let member = "parameters";
+  pub fn servers(&self) -> Option<Vec<(Vec<String>, Server)>> {
+    let member = "servers";
     Some(
       self
         .0
@@ -44,21 +42,21 @@ impl Operation {
     )
   }
 
-  pub fn bodies(&self) -> Option<BTreeMap<Vec<String>, Body>> {
-    let member = "requestBody";
-    let member_1 = "content";
+  pub fn request_parameters(
+    &self,
+  ) -> Option<BTreeMap<Vec<String>, NodeOrReference<RequestParameter>>> {
+    let member = "parameters";
     Some(
       self
         .0
         .as_object()?
         .get(member)?
-        .as_object()?
-        .get(member_1)?
-        .as_object()?
-        .into_iter()
+        .as_array()?
+        .iter()
+        .enumerate()
         .map(|(key, node)| {
           (
-            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            vec![member.to_owned(), key.to_string()],
             node.clone().into(),
           )
         })
@@ -66,6 +64,14 @@ impl Operation {
     )
   }
 
+  pub fn request_body(&self) -> Option<(Vec<String>, NodeOrReference<RequestBody>)> {
+    let member = "requestBody";
+    Some((
+      vec![member.to_owned()],
+      self.0.as_object()?.get(member)?.clone().into(),
+    ))
+  }
+
   pub fn operation_results(
     &self,
   ) -> Option<BTreeMap<Vec<String>, NodeOrReference<OperationResult>>> {
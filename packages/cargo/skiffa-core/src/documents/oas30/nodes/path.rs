@@ -6,6 +6,30 @@ use std::collections::BTreeMap;
 pub struct Path(serde_json::Value);
 
 impl Path {
+  pub fn reference(&self) -> Option<&str> {
+    self.0.as_object()?.get("$ref")?.as_str()
+  }
+
+  pub fn servers(&self) -> Option<Vec<(Vec<String>, Server)>> {
+    let member = "servers";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_array()?
+        .iter()
+        .enumerate()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), key.to_string()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+
   pub fn operations(&self) -> Option<BTreeMap<Vec<String>, Operation>> {
     Some(
       self
@@ -5,6 +5,17 @@ impl Reference {
   pub fn reference(&self) -> Option<&str> {
     self.0.as_object()?.get("$ref")?.as_str()
   }
+
+  /// In 3.1 (not in 3.0) a reference may override the summary and
+  /// description of the referenced node.
+  ///
+  pub fn summary(&self) -> Option<&str> {
+    self.0.as_object()?.get("summary")?.as_str()
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
 }
 
 impl From<serde_json::Value> for Reference {
@@ -0,0 +1,36 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct RequestBody(serde_json::Value);
+
+impl RequestBody {
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn required(&self) -> Option<bool> {
+    self.0.as_object()?.get("required")?.as_bool()
+  }
+
+  pub fn bodies(&self) -> Option<BTreeMap<Vec<String>, Body>> {
+    let member = "content";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for RequestBody {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -3,11 +3,57 @@ pub struct RequestParameter(serde_json::Value);
 
 impl RequestParameter {
   pub fn schema_pointer(&self) -> Option<Vec<String>> {
+    let node = self.0.as_object()?;
+    if node.contains_key("schema") {
+      return Some(vec!["schema".to_owned()]);
+    }
+
+    /*
+    a parameter may be defined via content instead of schema, content has
+    exactly one entry
+    */
+    let (content_type, media_type_node) = node.get("content")?.as_object()?.iter().next()?;
+    media_type_node.as_object()?.get("schema")?;
+    Some(vec![
+      "content".to_owned(),
+      content_type.to_owned(),
+      "schema".to_owned(),
+    ])
+  }
+
+  pub fn content_type(&self) -> Option<&str> {
     self
       .0
       .as_object()?
-      .get("schema")
-      .map(|_value| vec!["schema".to_owned()])
+      .get("content")?
+      .as_object()?
+      .keys()
+      .next()
+      .map(|key| key.as_str())
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn deprecated(&self) -> Option<bool> {
+    self.0.as_object()?.get("deprecated")?.as_bool()
+  }
+
+  pub fn style(&self) -> Option<&str> {
+    self.0.as_object()?.get("style")?.as_str()
+  }
+
+  pub fn explode(&self) -> Option<bool> {
+    self.0.as_object()?.get("explode")?.as_bool()
+  }
+
+  pub fn allow_reserved(&self) -> Option<bool> {
+    self.0.as_object()?.get("allowReserved")?.as_bool()
+  }
+
+  pub fn allow_empty_value(&self) -> Option<bool> {
+    self.0.as_object()?.get("allowEmptyValue")?.as_bool()
   }
 
   pub fn r#in(&self) -> Option<&str> {
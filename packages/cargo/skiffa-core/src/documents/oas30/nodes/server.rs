@@ -0,0 +1,34 @@
+use super::*;
+
+#[derive(Clone)]
+pub struct Server(serde_json::Value);
+
+impl Server {
+  pub fn url(&self) -> Option<&str> {
+    self.0.as_object()?.get("url")?.as_str()
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn variables(&self) -> Option<Vec<(Vec<String>, ServerVariable)>> {
+    let member = "variables";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for Server {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
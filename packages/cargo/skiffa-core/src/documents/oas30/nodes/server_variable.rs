@@ -0,0 +1,31 @@
+#[derive(Clone)]
+pub struct ServerVariable(serde_json::Value);
+
+impl ServerVariable {
+  pub fn default(&self) -> Option<&str> {
+    self.0.as_object()?.get("default")?.as_str()
+  }
+
+  pub fn r#enum(&self) -> Option<Vec<String>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .get("enum")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| Some(value.as_str()?.to_owned()))
+        .collect(),
+    )
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+}
+
+impl From<serde_json::Value> for ServerVariable {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -1,3 +1,4 @@
 mod document;
+mod nodes;
 
 pub use document::*;
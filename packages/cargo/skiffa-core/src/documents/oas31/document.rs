@@ -1,45 +1,204 @@
+use super::nodes;
+use crate::documents::oas30;
 use crate::utils::NodeLocation;
 use crate::{
-  documents::{DocumentContext, DocumentError, DocumentInterface},
+  documents::{
+    make_meta_schema_validator, validate_node, Diagnostic, DocumentContext, DocumentError,
+    DocumentInterface,
+  },
   models,
 };
-use std::rc;
+use once_cell::sync::Lazy;
+use std::{iter, rc};
 
-#[allow(dead_code)]
+static META_SCHEMA_VALIDATOR: Lazy<jsonschema::Validator> =
+  Lazy::new(|| make_meta_schema_validator(include_str!("schema.json")));
+
+/// A 3.1 document is read like a 3.0 document. This document only adds what
+/// was introduced in 3.1: webhooks, path item components and the json schema
+/// dialect. The 3.0 document it wraps lets references override the summary
+/// and description of the referenced node.
+///
 pub struct Document {
-  retrieval_location: NodeLocation,
-  node: serde_json::Value,
+  base: oas30::Document,
 }
 
 impl Document {
   pub fn new(context: rc::Weak<DocumentContext>, retrieval_location: NodeLocation) -> Self {
-    let context = context.upgrade().unwrap();
-    let node = context.get_node(&retrieval_location).unwrap();
     Self {
-      retrieval_location,
-      node: node.clone(),
+      base: oas30::Document::new_with_summary_and_description_overrides(
+        context,
+        retrieval_location,
+      ),
     }
   }
 }
 
 impl DocumentInterface for Document {
   fn get_default_schema_id(&self) -> String {
-    "https://spec.openapis.org/oas/3.1/dialect/base".to_owned()
+    self
+      .base
+      .get_node::<nodes::Api>(&self.base.get_document_location())
+      .ok()
+      .and_then(|api_node| api_node.json_schema_dialect().map(Into::into))
+      .unwrap_or_else(|| "https://spec.openapis.org/oas/3.1/dialect/base".to_owned())
   }
 
   fn get_document_location(&self) -> NodeLocation {
-    self.retrieval_location.clone()
+    self.base.get_document_location()
   }
 
   fn get_api_model(&self) -> Result<rc::Rc<models::Api>, DocumentError> {
-    todo!()
+    let api_location = self.base.get_document_location();
+    let api_node = self.base.get_node(&api_location)?;
+
+    let mut api = self.base.make_api_model(api_location.clone(), api_node)?;
+    api.webhooks = self.make_webhook_models(&api_location, &api.servers)?;
+
+    Ok(rc::Rc::new(api))
+  }
+
+  fn validate(&self) -> Result<Vec<Diagnostic>, DocumentError> {
+    let context = self.base.get_context()?;
+    let api_location = self.base.get_document_location();
+    let api_node = context
+      .get_node(&api_location)
+      .ok_or(DocumentError::NodeNotFound)?;
+
+    Ok(validate_node(
+      &META_SCHEMA_VALIDATOR,
+      &api_location,
+      &api_node,
+    ))
   }
 
   fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
-    todo!()
+    let api_location = self.base.get_document_location();
+    let api_node = self.base.get_node(&api_location)?;
+
+    self
+      .base
+      .get_referenced_locations()?
+      .into_iter()
+      .map(Ok)
+      .chain(self.get_referenced_locations_from_api(api_location, api_node))
+      .collect()
   }
 
   fn get_schema_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
-    todo!()
+    let api_location = self.base.get_document_location();
+    let api_node = self.base.get_node(&api_location)?;
+
+    self
+      .base
+      .get_schema_locations()?
+      .into_iter()
+      .map(Ok)
+      .chain(self.get_schema_locations_from_api(api_location, api_node))
+      .collect()
+  }
+
+  fn get_derived_schemas(&self) -> Result<Vec<(NodeLocation, serde_json::Value)>, DocumentError> {
+    // every schema is a node of the document
+    Ok(Vec::new())
+  }
+}
+
+impl Document {
+  fn make_webhook_models(
+    &self,
+    api_location: &NodeLocation,
+    api_servers: &[rc::Rc<models::Server>],
+  ) -> Result<Vec<rc::Rc<models::Webhook>>, DocumentError> {
+    let context = self.base.get_context()?;
+    let base_api_node: oas30::nodes::Api = self.base.get_node(api_location)?;
+    let api_node: nodes::Api = self.base.get_node(api_location)?;
+
+    /*
+    webhook models that fail are reported and skipped, like path models
+    */
+    let webhooks = api_node
+      .webhooks()
+      .into_iter()
+      .flatten()
+      .filter_map(|(pointer, node)| {
+        let name = pointer.last().unwrap().clone();
+        let location = api_location.push_pointer(pointer);
+        let result = self.base.resolve_path(&location, node).and_then(|layers| {
+          self.make_webhook_model(
+            base_api_node.clone(),
+            api_servers,
+            location.clone(),
+            layers,
+            name,
+          )
+        });
+        context
+          .report_result(&location, "could not make the webhook model", result)
+          .map(rc::Rc::new)
+      })
+      .collect();
+
+    Ok(webhooks)
+  }
+
+  fn make_webhook_model(
+    &self,
+    api_node: oas30::nodes::Api,
+    api_servers: &[rc::Rc<models::Server>],
+    webhook_location: NodeLocation,
+    webhook_layers: Vec<(NodeLocation, nodes::Path)>,
+    name: String,
+  ) -> Result<models::Webhook, DocumentError> {
+    let servers = self
+      .base
+      .make_path_server_models(&webhook_layers, api_servers)?;
+    let operations = self
+      .base
+      .make_operation_models(api_node, &servers, &webhook_layers)?;
+
+    Ok(models::Webhook {
+      location: webhook_location.clone(),
+      name,
+      operations,
+    })
+  }
+}
+
+impl Document {
+  fn get_referenced_locations_from_api(
+    &self,
+    location: NodeLocation,
+    node: nodes::Api,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(oas30::Document::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.webhooks().into_iter().flatten(),
+        |location, node| self.base.get_referenced_locations_from_path(location, node),
+      ))
+      .chain(oas30::Document::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.path_item_components().into_iter().flatten(),
+        |location, node| self.base.get_referenced_locations_from_path(location, node),
+      ))
+  }
+
+  fn get_schema_locations_from_api(
+    &self,
+    location: NodeLocation,
+    node: nodes::Api,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(oas30::Document::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.path_item_components().into_iter().flatten(),
+        |location, node| self.base.get_schema_locations_from_path(location, node),
+      ))
+      .chain(oas30::Document::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.webhooks().into_iter().flatten(),
+        |location, node| self.base.get_schema_locations_from_path(location, node),
+      ))
   }
 }
@@ -0,0 +1,8 @@
+mod api;
+
+pub use api::*;
+
+/*
+all other nodes are the same as in 3.0
+*/
+pub use crate::documents::oas30::nodes::Path;
@@ -0,0 +1,56 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// The members of the api that were introduced in 3.1, all other members are
+/// read with the 3.0 api node.
+///
+#[derive(Clone)]
+pub struct Api(serde_json::Value);
+
+impl Api {
+  pub fn json_schema_dialect(&self) -> Option<&str> {
+    self.0.as_object()?.get("jsonSchemaDialect")?.as_str()
+  }
+
+  pub fn webhooks(&self) -> Option<BTreeMap<Vec<String>, Path>> {
+    let member = "webhooks";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn path_item_components(&self) -> Option<BTreeMap<Vec<String>, Path>> {
+    let member = "components";
+    let member_1 = "pathItems";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .get(member_1)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), member_1.to_owned(), key.clone()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for Api {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
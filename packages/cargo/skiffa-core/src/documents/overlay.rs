@@ -0,0 +1,282 @@
+use crate::error::Error;
+use crate::utils::JsonPath;
+
+/// An OpenAPI Overlay (1.0) document, a list of actions that update or
+/// remove nodes of another document. Actions are applied in order.
+///
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct Overlay {
+  /**
+   * version of the overlay specification
+   */
+  pub overlay: String,
+  pub actions: Vec<OverlayAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct OverlayAction {
+  /**
+   * JSONPath query that selects the nodes the action applies to
+   */
+  pub target: String,
+  pub description: Option<String>,
+  /**
+   * merged into the selected objects, or appended to the selected arrays
+   */
+  pub update: Option<serde_json::Value>,
+  /**
+   * the selected nodes are removed, the update is ignored if this is set
+   */
+  #[serde(default)]
+  pub remove: bool,
+}
+
+impl Overlay {
+  pub fn from_node(node: &serde_json::Value) -> Result<Self, Error> {
+    let overlay: Self =
+      serde_json::from_value(node.clone()).map_err(|_error| Error::SerializationError)?;
+
+    if !overlay.overlay.starts_with("1.") {
+      return Err(Error::DocumentTypeError);
+    }
+
+    Ok(overlay)
+  }
+}
+
+impl OverlayAction {
+  /// Applies the action to the node, returns the pointers of the nodes whose
+  /// descendants were changed, one for every node that was selected by the
+  /// target. That is the selected node itself, or the array the selected
+  /// node was removed from, because the other items of the array moved.
+  ///
+  pub fn apply(&self, node: &mut serde_json::Value) -> Result<Vec<Vec<String>>, Error> {
+    let target: JsonPath = self.target.parse()?;
+    let mut pointers: Vec<_> = target
+      .query(node)
+      .into_iter()
+      .map(|(pointer, _)| pointer)
+      .collect();
+
+    /*
+    overlapping selectors may select a node more than once, but the action is
+    applied to every node once
+    */
+    pointers.sort_by(|left, right| compare_pointers(left, right));
+    pointers.dedup();
+
+    if self.remove {
+      /*
+      a node under another selected node is removed with that node
+      */
+      let selected_pointers = pointers.clone();
+      pointers.retain(|pointer| {
+        !selected_pointers
+          .iter()
+          .any(|other| other.len() < pointer.len() && pointer.starts_with(other))
+      });
+
+      /*
+      remove the last array items first, so the indices of the other items
+      do not change
+      */
+      let changed_pointers = pointers
+        .into_iter()
+        .rev()
+        .map(|pointer| remove_node(node, pointer))
+        .collect();
+      return Ok(changed_pointers);
+    }
+
+    if let Some(update) = &self.update {
+      for pointer in &pointers {
+        let target_node = get_node_mut(node, pointer).ok_or(Error::NotFound)?;
+        match target_node {
+          serde_json::Value::Object(_) => merge_node(target_node, update),
+          serde_json::Value::Array(array_node) => array_node.push(update.clone()),
+          _ => return Err(Error::Conflict),
+        }
+      }
+    }
+
+    Ok(pointers)
+  }
+}
+
+/**
+members of the update replace the members of the node, except objects that
+are merged
+*/
+fn merge_node(node: &mut serde_json::Value, update: &serde_json::Value) {
+  match (node, update) {
+    (serde_json::Value::Object(object_node), serde_json::Value::Object(update_node)) => {
+      for (member, update_member_node) in update_node {
+        match object_node.get_mut(member) {
+          Some(member_node) if member_node.is_object() && update_member_node.is_object() => {
+            merge_node(member_node, update_member_node)
+          }
+          _ => {
+            object_node.insert(member.clone(), update_member_node.clone());
+          }
+        }
+      }
+    }
+    (node, update) => *node = update.clone(),
+  }
+}
+
+fn get_node_mut<'a>(
+  node: &'a mut serde_json::Value,
+  pointer: &[String],
+) -> Option<&'a mut serde_json::Value> {
+  pointer.iter().try_fold(node, |node, member| match node {
+    serde_json::Value::Object(object_node) => object_node.get_mut(member),
+    serde_json::Value::Array(array_node) => array_node.get_mut(member.parse::<usize>().ok()?),
+    _ => None,
+  })
+}
+
+/**
+remove the node at the pointer, returns the pointer of the node whose
+descendants changed
+*/
+fn remove_node(node: &mut serde_json::Value, pointer: Vec<String>) -> Vec<String> {
+  let Some((member, parent_pointer)) = pointer.split_last() else {
+    return pointer;
+  };
+
+  match get_node_mut(node, parent_pointer) {
+    Some(serde_json::Value::Object(object_node)) => {
+      object_node.remove(member);
+      pointer
+    }
+    Some(serde_json::Value::Array(array_node)) => {
+      if let Some(index) = member
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index < array_node.len())
+      {
+        array_node.remove(index);
+      }
+      parent_pointer.to_vec()
+    }
+    _ => pointer,
+  }
+}
+
+/**
+compare pointers member by member, array indices are compared as numbers
+*/
+fn compare_pointers(left: &[String], right: &[String]) -> std::cmp::Ordering {
+  for (left, right) in left.iter().zip(right) {
+    let ordering = match (left.parse::<usize>(), right.parse::<usize>()) {
+      (Ok(left), Ok(right)) => left.cmp(&right),
+      _ => left.cmp(right),
+    };
+    if ordering.is_ne() {
+      return ordering;
+    }
+  }
+  left.len().cmp(&right.len())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_overlay() {
+    let overlay = Overlay::from_node(&serde_json::json!({
+      "overlay": "1.0.0",
+      "info": { "title": "test", "version": "1.0.0" },
+      "actions": [
+        {
+          "target": "$.info",
+          "update": { "description": "updated", "contact": { "name": "me" } },
+        },
+        {
+          "target": "$.tags",
+          "update": { "name": "added" },
+        },
+        {
+          "target": "$.tags[?@.name == 'a' || @.name == 'c']",
+          "remove": true,
+        },
+      ],
+    }))
+    .unwrap();
+
+    let mut node = serde_json::json!({
+      "info": { "title": "api", "contact": { "email": "me@example.com" } },
+      "tags": [{ "name": "a" }, { "name": "b" }, { "name": "c" }],
+    });
+    let counts: Vec<_> = overlay
+      .actions
+      .iter()
+      .map(|action| action.apply(&mut node).unwrap().len())
+      .collect();
+
+    assert_eq!(counts, vec![1, 1, 2]);
+    assert_eq!(
+      node,
+      serde_json::json!({
+        "info": {
+          "title": "api",
+          "description": "updated",
+          "contact": { "email": "me@example.com", "name": "me" },
+        },
+        "tags": [{ "name": "b" }, { "name": "added" }],
+      })
+    );
+
+    assert_eq!(
+      Overlay::from_node(&serde_json::json!({ "overlay": "2.0.0", "actions": [] })),
+      Err(Error::DocumentTypeError)
+    );
+  }
+
+  #[test]
+  fn test_overlay_overlapping_targets() {
+    let overlay = Overlay::from_node(&serde_json::json!({
+      "overlay": "1.0.0",
+      "info": { "title": "test", "version": "1.0.0" },
+      "actions": [
+        {
+          "target": "$['tags', 'tags']",
+          "update": "d",
+        },
+        {
+          "target": "$.tags[0, 0]",
+          "remove": true,
+        },
+        {
+          "target": "$..[?@.deprecated == true]",
+          "remove": true,
+        },
+      ],
+    }))
+    .unwrap();
+
+    let mut node = serde_json::json!({
+      "tags": ["a", "b", "c"],
+      "paths": [
+        { "deprecated": true, "get": { "deprecated": true } },
+        { "get": { "deprecated": false } },
+      ],
+    });
+    let counts: Vec<_> = overlay
+      .actions
+      .iter()
+      .map(|action| action.apply(&mut node).unwrap().len())
+      .collect();
+
+    assert_eq!(counts, vec![1, 1, 1]);
+    assert_eq!(
+      node,
+      serde_json::json!({
+        "tags": ["b", "c", "d"],
+        "paths": [{ "get": { "deprecated": false } }],
+      })
+    );
+  }
+}
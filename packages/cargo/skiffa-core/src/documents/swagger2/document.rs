@@ -1,27 +1,137 @@
-use crate::utils::NodeLocation;
+use super::nodes;
+use crate::documents::oas30::{dereference_node, merge_parameter_nodes, resolve_path_node};
+use crate::models::{AuthenticationRequirement, AuthenticationRequirementGroup, StatusKind};
+use crate::utils::{resolve_server_url, NodeLocation};
 use crate::{
-  documents::{DocumentContext, DocumentError, DocumentInterface},
+  documents::{
+    make_meta_schema_validator, validate_node, Diagnostic, DiagnosticSeverity, DocumentContext,
+    DocumentError, DocumentInterface,
+  },
   models,
 };
-use std::rc;
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, BTreeSet};
+use std::{iter, rc};
+
+const DEFAULT_CONTENT_TYPE: &str = "application/json";
+const FORM_CONTENT_TYPES: [&str; 2] = ["application/x-www-form-urlencoded", "multipart/form-data"];
+
+static META_SCHEMA_VALIDATOR: Lazy<jsonschema::Validator> =
+  Lazy::new(|| make_meta_schema_validator(include_str!("schema.json")));
 
-#[allow(dead_code)]
 pub struct Document {
+  context: rc::Weak<DocumentContext>,
   retrieval_location: NodeLocation,
-  node: serde_json::Value,
 }
 
 impl Document {
   pub fn new(context: rc::Weak<DocumentContext>, retrieval_location: NodeLocation) -> Self {
-    let context = context.upgrade().unwrap();
-    let node = context.get_node(&retrieval_location).unwrap();
     Self {
+      context,
       retrieval_location,
-      node: node.clone(),
     }
   }
 }
 
+impl Document {
+  fn get_referenced_locations_from_reference_entries<N>(
+    location: NodeLocation,
+    entries: impl Iterator<Item = (Vec<String>, nodes::NodeOrReference<N>)>,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>>
+  where
+    N: From<serde_json::Value>,
+  {
+    entries
+      .filter_map(move |(pointer, node)| {
+        let location = location.push_pointer(pointer);
+        if let nodes::NodeOrReference::Reference(reference) = node {
+          Some((location, reference))
+        } else {
+          None
+        }
+      })
+      .filter_map(|(_location, reference)| {
+        let reference = reference.reference()?;
+        Some(reference.parse().map_err(Into::into))
+      })
+  }
+
+  fn get_sub_locations_from_node_entries<N, SR>(
+    location: NodeLocation,
+    entries: impl Iterator<Item = (Vec<String>, N)>,
+    selector: impl Fn(NodeLocation, N) -> SR,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>>
+  where
+    N: From<serde_json::Value>,
+    SR: Iterator<Item = Result<NodeLocation, DocumentError>>,
+  {
+    entries
+      .map(move |(pointer, node)| {
+        let location = location.push_pointer(pointer);
+        (location, node)
+      })
+      .flat_map(move |(location, node)| (selector)(location, node))
+  }
+
+  /**
+  the context owns the document, so the context should always be available
+  while the document is used
+  */
+  fn get_context(&self) -> Result<rc::Rc<DocumentContext>, DocumentError> {
+    self
+      .context
+      .upgrade()
+      .ok_or(DocumentError::ContextUnavailable)
+  }
+
+  fn get_node<T>(&self, location: &NodeLocation) -> Result<T, DocumentError>
+  where
+    T: From<serde_json::Value>,
+  {
+    let context = self.get_context()?;
+    let node = context
+      .get_node(location)
+      .ok_or(DocumentError::NodeNotFound)?;
+    let node: T = node.clone().into();
+    Ok(node)
+  }
+
+  fn dereference<T>(
+    &self,
+    location: &NodeLocation,
+    node: nodes::NodeOrReference<T>,
+  ) -> Result<(NodeLocation, T), DocumentError>
+  where
+    T: From<serde_json::Value>,
+  {
+    let context = self.get_context()?;
+    dereference_node(&context, location, node, false)
+  }
+
+  fn resolve_path(
+    &self,
+    location: &NodeLocation,
+    node: nodes::Path,
+  ) -> Result<Vec<(NodeLocation, nodes::Path)>, DocumentError> {
+    let context = self.get_context()?;
+    resolve_path_node(&context, location, node, nodes::Path::reference)
+  }
+
+  fn merge_parameter_nodes(
+    &self,
+    parameter_nodes: &[(NodeLocation, nodes::RequestParameter)],
+    override_parameter_nodes: Vec<(NodeLocation, nodes::RequestParameter)>,
+  ) -> Result<Vec<(NodeLocation, nodes::RequestParameter)>, DocumentError> {
+    let context = self.get_context()?;
+    merge_parameter_nodes(
+      &context,
+      parameter_nodes,
+      override_parameter_nodes,
+      |node| (node.r#in(), node.name()),
+    )
+  }
+}
+
 impl DocumentInterface for Document {
   fn get_default_schema_id(&self) -> String {
     "http://swagger.io/v2/schema.json#/definitions/schema".to_owned()
@@ -32,14 +142,1006 @@ impl DocumentInterface for Document {
   }
 
   fn get_api_model(&self) -> Result<rc::Rc<models::Api>, DocumentError> {
-    todo!()
+    let api_location = self.retrieval_location.clone();
+    let api_node = self.get_node(&api_location)?;
+
+    self.make_api_model(api_location, api_node).map(rc::Rc::new)
+  }
+
+  fn validate(&self) -> Result<Vec<Diagnostic>, DocumentError> {
+    let context = self.get_context()?;
+    let api_node = context
+      .get_node(&self.retrieval_location)
+      .ok_or(DocumentError::NodeNotFound)?;
+
+    Ok(validate_node(
+      &META_SCHEMA_VALIDATOR,
+      &self.retrieval_location,
+      &api_node,
+    ))
   }
 
   fn get_referenced_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
-    todo!()
+    let api_location = self.retrieval_location.clone();
+    let api_node = self.get_node(&api_location)?;
+
+    self
+      .get_referenced_locations_from_api(api_location, api_node)
+      .collect()
   }
 
   fn get_schema_locations(&self) -> Result<Vec<NodeLocation>, DocumentError> {
-    todo!()
+    let api_location = self.retrieval_location.clone();
+    let api_node = self.get_node(&api_location)?;
+
+    self
+      .get_schema_locations_from_api(api_location, api_node)
+      .collect()
+  }
+
+  fn get_derived_schemas(&self) -> Result<Vec<(NodeLocation, serde_json::Value)>, DocumentError> {
+    let api_location = self.retrieval_location.clone();
+    let api_node = self.get_node(&api_location)?;
+
+    self.get_derived_schemas_from_api(api_location, api_node)
+  }
+}
+
+impl Document {
+  fn make_api_model(
+    &self,
+    api_location: NodeLocation,
+    api_node: nodes::Api,
+  ) -> Result<models::Api, DocumentError> {
+    let servers = self.make_server_models(&api_location, &api_node, api_node.schemes())?;
+
+    let context = self.get_context()?;
+
+    /*
+    path and authentication models that fail are reported and skipped, so one
+    error does not prevent the rest of the api from being used
+    */
+    let paths = api_node
+      .paths()
+      .into_iter()
+      .flatten()
+      .filter(|(pointer, _node)| !pointer.last().unwrap().starts_with("x-"))
+      .enumerate()
+      .filter_map(|(index, (pointer, node))| {
+        let pattern = pointer.last().unwrap().clone();
+        let id = index + 1;
+        let location = api_location.push_pointer(pointer);
+        let result = self.resolve_path(&location, node).and_then(|layers| {
+          self.make_path_model(
+            api_node.clone(),
+            &servers,
+            location.clone(),
+            layers,
+            id,
+            pattern,
+          )
+        });
+        context
+          .report_result(&location, "could not make the path model", result)
+          .map(rc::Rc::new)
+      })
+      .collect();
+
+    let authentication = api_node
+      .security_schemes()
+      .into_iter()
+      .flatten()
+      .filter_map(|(pointer, node)| {
+        let name = pointer.last().unwrap().clone();
+        let location = api_location.push_pointer(pointer);
+        let result = self.make_authentication_model(location.clone(), node, name);
+        context
+          .report_result(&location, "could not make the authentication model", result)
+          .map(rc::Rc::new)
+      })
+      .collect();
+
+    Ok(models::Api {
+      location: api_location.clone(),
+      servers,
+      paths,
+      // webhooks were introduced in openapi 3.1
+      webhooks: Vec::new(),
+      authentication,
+    })
+  }
+
+  fn make_path_model(
+    &self,
+    api_node: nodes::Api,
+    api_servers: &[rc::Rc<models::Server>],
+    path_location: NodeLocation,
+    path_layers: Vec<(NodeLocation, nodes::Path)>,
+    id: usize,
+    pattern: String,
+  ) -> Result<models::Path, DocumentError> {
+    let path_parameter_nodes = self.get_path_parameter_nodes(&path_layers)?;
+    let operation_nodes = Self::get_operation_nodes(&path_layers);
+
+    let context = self.get_context()?;
+    let operations = operation_nodes
+      .into_iter()
+      .filter_map(|(method, (location, node))| {
+        let result = method
+          .as_str()
+          .parse()
+          .map_err(DocumentError::from)
+          .and_then(|method| {
+            self.make_operation_model(
+              api_node.clone(),
+              api_servers,
+              &path_parameter_nodes,
+              location.clone(),
+              node,
+              method,
+            )
+          });
+        context
+          .report_result(&location, "could not make the operation model", result)
+          .map(rc::Rc::new)
+      })
+      .collect();
+
+    Ok(models::Path {
+      id,
+      location: path_location.clone(),
+      pattern,
+      // swagger 2 has no servers on the path level
+      servers: api_servers.to_vec(),
+      operations,
+    })
+  }
+
+  /**
+  parameters on the referencing path item override the parameters on the
+  referenced path item
+  */
+  fn get_path_parameter_nodes(
+    &self,
+    path_layers: &[(NodeLocation, nodes::Path)],
+  ) -> Result<Vec<(NodeLocation, nodes::RequestParameter)>, DocumentError> {
+    path_layers
+      .iter()
+      .map(|(location, node)| {
+        node
+          .request_parameters()
+          .into_iter()
+          .flatten()
+          .map(|(pointer, node)| {
+            let location = location.push_pointer(pointer);
+            self.dereference(&location, node)
+          })
+          .collect::<Result<Vec<_>, DocumentError>>()
+      })
+      .try_fold(Vec::new(), |parameter_nodes, layer_parameter_nodes| {
+        layer_parameter_nodes.and_then(|layer_parameter_nodes| {
+          self.merge_parameter_nodes(&parameter_nodes, layer_parameter_nodes)
+        })
+      })
+  }
+
+  /**
+  operations on the referencing path item override the operations on the
+  referenced path item
+  */
+  fn get_operation_nodes(
+    path_layers: &[(NodeLocation, nodes::Path)],
+  ) -> BTreeMap<String, (NodeLocation, nodes::Operation)> {
+    path_layers
+      .iter()
+      .flat_map(|(location, node)| {
+        node
+          .operations()
+          .into_iter()
+          .flatten()
+          .map(move |(pointer, node)| {
+            let method = pointer.last().unwrap().clone();
+            (method, (location.push_pointer(pointer), node))
+          })
+      })
+      .collect()
+  }
+
+  /**
+  operation parameters override path parameters with the same name and
+  location
+  */
+  fn get_operation_parameter_nodes(
+    &self,
+    path_parameter_nodes: &[(NodeLocation, nodes::RequestParameter)],
+    operation_location: &NodeLocation,
+    operation_node: &nodes::Operation,
+  ) -> Result<Vec<(NodeLocation, nodes::RequestParameter)>, DocumentError> {
+    let operation_parameter_nodes = operation_node
+      .request_parameters()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let location = operation_location.push_pointer(pointer);
+        self.dereference(&location, node)
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    self.merge_parameter_nodes(path_parameter_nodes, operation_parameter_nodes)
+  }
+
+  /**
+  form data parameters together make up the body of a form, the schema of
+  that body is an object with a property for every parameter. Parameters
+  without a schema are reported and left out. Returns None if there are no
+  form data parameters.
+  */
+  fn make_form_data_schema(
+    &self,
+    parameter_nodes: &[(NodeLocation, nodes::RequestParameter)],
+  ) -> Result<Option<serde_json::Value>, DocumentError> {
+    let mut form_data_parameter_nodes = parameter_nodes
+      .iter()
+      .filter(|(_location, node)| node.r#in() == Some("formData"))
+      .peekable();
+    if form_data_parameter_nodes.peek().is_none() {
+      return Ok(None);
+    }
+
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for (location, node) in form_data_parameter_nodes {
+      let Some(name) = node.name() else {
+        continue;
+      };
+      let Some(mut schema) = node.schema() else {
+        let context = self.get_context()?;
+        context.report_diagnostic(Diagnostic {
+          location: location.clone(),
+          message: format!("form data parameter {} has no type", name),
+          severity: DiagnosticSeverity::Warning,
+          cause: None,
+        });
+        continue;
+      };
+
+      if let (Some(description), Some(schema)) = (node.description(), schema.as_object_mut()) {
+        schema.insert("description".to_owned(), description.into());
+      }
+      properties.insert(name.to_owned(), schema);
+      if node.required().unwrap_or(false) {
+        required.push(serde_json::Value::from(name));
+      }
+    }
+
+    let mut schema = serde_json::Map::new();
+    schema.insert("type".to_owned(), "object".into());
+    schema.insert("properties".to_owned(), properties.into());
+    if !required.is_empty() {
+      schema.insert("required".to_owned(), required.into());
+    }
+
+    Ok(Some(schema.into()))
+  }
+
+  fn make_operation_model(
+    &self,
+    api_node: nodes::Api,
+    path_servers: &[rc::Rc<models::Server>],
+    path_parameter_nodes: &[(NodeLocation, nodes::RequestParameter)],
+    operation_location: NodeLocation,
+    operation_node: nodes::Operation,
+    method: models::Method,
+  ) -> Result<models::Operation, DocumentError> {
+    let mut status_codes_available = (100..600).collect();
+    let servers = match operation_node.schemes() {
+      Some(schemes) => self.make_server_models(&operation_location, &api_node, Some(schemes))?,
+      None => path_servers.to_vec(),
+    };
+    let authentication_requirements = None
+      .or_else(|| operation_node.security())
+      .or_else(|| api_node.security())
+      .into_iter()
+      .flatten()
+      .map(|requirements| self.make_authentication_requirement_group(requirements))
+      .map(rc::Rc::new)
+      .collect();
+
+    let consumes = None
+      .or_else(|| operation_node.consumes())
+      .or_else(|| api_node.consumes())
+      .filter(|content_types| !content_types.is_empty())
+      .unwrap_or_else(|| vec![DEFAULT_CONTENT_TYPE.to_owned()]);
+
+    let produces = None
+      .or_else(|| operation_node.produces())
+      .or_else(|| api_node.produces())
+      .filter(|content_types| !content_types.is_empty())
+      .unwrap_or_else(|| vec![DEFAULT_CONTENT_TYPE.to_owned()]);
+
+    let all_parameter_nodes = self.get_operation_parameter_nodes(
+      path_parameter_nodes,
+      &operation_location,
+      &operation_node,
+    )?;
+
+    let cookie_parameters = Vec::new();
+
+    let header_parameters = all_parameter_nodes
+      .iter()
+      .filter_map(|(location, node)| {
+        if node.r#in()? == "header" {
+          Some(
+            self
+              .make_parameter_model_request(location.clone(), node.clone())
+              .map(rc::Rc::new),
+          )
+        } else {
+          None
+        }
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    let path_parameters = all_parameter_nodes
+      .iter()
+      .filter_map(|(location, node)| {
+        if node.r#in()? == "path" {
+          Some(
+            self
+              .make_parameter_model_request(location.clone(), node.clone())
+              .map(rc::Rc::new),
+          )
+        } else {
+          None
+        }
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    let query_parameters = all_parameter_nodes
+      .iter()
+      .filter_map(|(location, node)| {
+        if node.r#in()? == "query" {
+          Some(
+            self
+              .make_parameter_model_request(location.clone(), node.clone())
+              .map(rc::Rc::new),
+          )
+        } else {
+          None
+        }
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    /*
+    there can be only one body parameter, it is serialized with every
+    content type the operation consumes
+    */
+    let body_bodies = all_parameter_nodes
+      .iter()
+      .filter(|(_location, node)| node.r#in() == Some("body"))
+      .take(1)
+      .flat_map(|(location, node)| {
+        consumes
+          .iter()
+          .filter(|content_type| !FORM_CONTENT_TYPES.contains(&content_type.as_str()))
+          .map(|content_type| {
+            self
+              .make_body_model_request(location.clone(), node.clone(), content_type.clone())
+              .map(rc::Rc::new)
+          })
+      });
+
+    /*
+    form data parameters together make up a body with a form content type,
+    the schema of that body is derived from the parameters and has the
+    location of the operation
+    */
+    let form_data_bodies = self
+      .make_form_data_schema(&all_parameter_nodes)?
+      .into_iter()
+      .flat_map(|_schema| {
+        let mut content_types: Vec<_> = consumes
+          .iter()
+          .filter(|content_type| FORM_CONTENT_TYPES.contains(&content_type.as_str()))
+          .cloned()
+          .collect();
+        if content_types.is_empty() {
+          content_types.push(FORM_CONTENT_TYPES[0].to_owned());
+        }
+        content_types.into_iter().map(|content_type| {
+          Ok(rc::Rc::new(models::Body {
+            location: operation_location.clone(),
+            content_type,
+            schema_id: Some(operation_location.clone()),
+          }))
+        })
+      });
+
+    /*
+    the body is required if the body parameter or any of the form data
+    parameters is required
+    */
+    let body_required = all_parameter_nodes
+      .iter()
+      .filter(|(_location, node)| matches!(node.r#in(), Some("body") | Some("formData")))
+      .any(|(_location, node)| node.required().unwrap_or(false));
+
+    let body_description = all_parameter_nodes
+      .iter()
+      .find(|(_location, node)| node.r#in() == Some("body"))
+      .and_then(|(_location, node)| node.description())
+      .map(Into::into);
+
+    let bodies = iter::empty()
+      .chain(body_bodies)
+      .chain(form_data_bodies)
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    let mut operation_results = operation_node
+      .operation_results()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let status_kind: StatusKind = pointer.last().unwrap().clone().parse()?;
+        let location = operation_location.push_pointer(pointer);
+        let (location, node) = self.dereference(&location, node)?;
+        Ok((status_kind, location, node))
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    // order is important here, we want to take things from the status_codes_available in the
+    // right order, so that is status codes first, then classes, then what is left is default
+    operation_results.sort_by_key(|(status_kind, _location, _node)| *status_kind);
+
+    let operation_results = operation_results
+      .into_iter()
+      .map(|(status_kind, location, node)| {
+        self
+          .make_operation_result_model(
+            location,
+            node.clone(),
+            status_kind,
+            &produces,
+            &mut status_codes_available,
+          )
+          .map(rc::Rc::new)
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    Ok(models::Operation {
+      location: operation_location.clone(),
+      /*
+      operations without an operationId are named by the context when the api
+      model is complete, so names can be checked for collisions
+      */
+      name: operation_node.name().map(Into::into).unwrap_or_default(),
+      operation_id: operation_node.name().map(Into::into),
+      summary: operation_node.summary().map(Into::into),
+      description: operation_node.description().map(Into::into),
+      deprecated: operation_node.deprecated().unwrap_or(false),
+      servers,
+      method,
+      body_required,
+      body_description,
+      authentication_requirements,
+      cookie_parameters,
+      header_parameters,
+      path_parameters,
+      query_parameters,
+      bodies,
+      operation_results,
+    })
+  }
+
+  /// Swagger 2 does not have servers, but it has a host, a base path and
+  /// schemes. We make a server for every scheme. The servers are located at
+  /// the node that defines the schemes, the api or an operation.
+  ///
+  fn make_server_models(
+    &self,
+    location: &NodeLocation,
+    api_node: &nodes::Api,
+    schemes: Option<Vec<String>>,
+  ) -> Result<Vec<rc::Rc<models::Server>>, DocumentError> {
+    let base_path = api_node.base_path().unwrap_or("/");
+
+    /*
+    without a host, the host that serves the document that defines the
+    servers is used
+    */
+    let Some(host) = api_node.host() else {
+      return Ok(vec![rc::Rc::new(models::Server {
+        location: location.clone(),
+        url: resolve_server_url(location, base_path),
+        description: None,
+        variables: Vec::new(),
+      })]);
+    };
+
+    /*
+    without schemes, the scheme that is used to access the document is used
+    */
+    let schemes = schemes
+      .filter(|schemes| !schemes.is_empty())
+      .unwrap_or_else(|| {
+        let document_url = location.to_fetch_string().to_lowercase();
+        if document_url.starts_with("https://") {
+          vec!["https".to_owned()]
+        } else {
+          vec!["http".to_owned()]
+        }
+      });
+
+    Ok(
+      schemes
+        .into_iter()
+        .map(|scheme| {
+          rc::Rc::new(models::Server {
+            location: location.clone(),
+            url: format!("{}://{}{}", scheme, host, base_path),
+            description: None,
+            variables: Vec::new(),
+          })
+        })
+        .collect(),
+    )
+  }
+
+  fn make_operation_result_model(
+    &self,
+    operation_result_location: NodeLocation,
+    operation_result_node: nodes::OperationResult,
+    status_kind: StatusKind,
+    produces: &[String],
+    status_codes_available: &mut BTreeSet<usize>,
+  ) -> Result<models::OperationResult, DocumentError> {
+    let status_codes = status_kind
+      .into_iter()
+      .filter(|value| status_codes_available.remove(value))
+      .collect();
+
+    let header_parameters = operation_result_node
+      .response_headers()
+      .into_iter()
+      .flatten()
+      .map(|(pointer, node)| {
+        let name = pointer.last().unwrap().clone();
+        let location = operation_result_location.push_pointer(pointer);
+        self
+          .make_parameter_model_response(location, node, name)
+          .map(rc::Rc::new)
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    /*
+    a response without a schema has no body
+    */
+    let bodies = operation_result_node
+      .schema_pointer()
+      .into_iter()
+      .flat_map(|pointer| {
+        let location = operation_result_location.clone();
+        let schema_id = location.push_pointer(pointer);
+        produces.iter().map(move |content_type| {
+          Ok(rc::Rc::new(models::Body {
+            location: location.clone(),
+            content_type: content_type.clone(),
+            schema_id: Some(schema_id.clone()),
+          }))
+        })
+      })
+      .collect::<Result<Vec<_>, DocumentError>>()?;
+
+    Ok(models::OperationResult {
+      location: operation_result_location.clone(),
+      description: operation_result_node.description().map(Into::into),
+      status_kind,
+      status_codes,
+      header_parameters,
+      bodies,
+    })
+  }
+
+  fn make_body_model_request(
+    &self,
+    parameter_location: NodeLocation,
+    parameter_node: nodes::RequestParameter,
+    content_type: String,
+  ) -> Result<models::Body, DocumentError> {
+    let schema_id = parameter_node
+      .schema_pointer()
+      .map(|pointer| parameter_location.push_pointer(pointer));
+
+    Ok(models::Body {
+      location: parameter_location.clone(),
+      content_type,
+      schema_id,
+    })
+  }
+
+  fn make_parameter_model_request(
+    &self,
+    parameter_location: NodeLocation,
+    parameter_node: nodes::RequestParameter,
+  ) -> Result<models::Parameter, DocumentError> {
+    /*
+    the derived schema of the parameter has the location of the parameter
+    */
+    let schema_id = parameter_node
+      .schema()
+      .map(|_schema| parameter_location.clone());
+
+    let r#in = parameter_node.r#in().unwrap_or_default();
+    let (style, explode) = Self::get_parameter_style(r#in, parameter_node.collection_format());
+
+    Ok(models::Parameter {
+      location: parameter_location.clone(),
+      name: parameter_node
+        .name()
+        .map(Into::into)
+        .ok_or(DocumentError::MissingField)?,
+      description: parameter_node.description().map(Into::into),
+      // path parameters are always required
+      required: r#in == "path" || parameter_node.required().unwrap_or(false),
+      deprecated: false,
+      style,
+      explode,
+      allow_reserved: false,
+      allow_empty_value: parameter_node.allow_empty_value().unwrap_or(false),
+      content_type: None,
+      schema_id,
+    })
+  }
+
+  fn make_parameter_model_response(
+    &self,
+    header_location: NodeLocation,
+    header_node: nodes::ResponseHeader,
+    name: String,
+  ) -> Result<models::Parameter, DocumentError> {
+    /*
+    the derived schema of the header has the location of the header
+    */
+    let schema_id = header_node.schema().map(|_schema| header_location.clone());
+
+    let (style, explode) = Self::get_parameter_style("header", header_node.collection_format());
+
+    Ok(models::Parameter {
+      location: header_location.clone(),
+      name,
+      description: header_node.description().map(Into::into),
+      // swagger 2 has no notion of required response headers
+      required: false,
+      deprecated: false,
+      style,
+      explode,
+      allow_reserved: false,
+      allow_empty_value: false,
+      content_type: None,
+      schema_id,
+    })
+  }
+
+  /// Swagger 2 has a collection format instead of a style and explode. The
+  /// default collection format is csv. The tsv collection format has no
+  /// equivalent in openapi 3, it is treated like csv.
+  ///
+  fn get_parameter_style(
+    r#in: &str,
+    collection_format: Option<&str>,
+  ) -> (models::ParameterStyle, bool) {
+    match collection_format.unwrap_or("csv") {
+      "multi" => (models::ParameterStyle::Form, true),
+      "ssv" => (models::ParameterStyle::SpaceDelimited, false),
+      "pipes" => (models::ParameterStyle::PipeDelimited, false),
+      _ => (models::ParameterStyle::default_for(r#in), false),
+    }
+  }
+
+  fn make_authentication_model(
+    &self,
+    security_scheme_location: NodeLocation,
+    security_scheme_node: nodes::SecurityScheme,
+    name: String,
+  ) -> Result<models::Authentication, DocumentError> {
+    /*
+    basic authentication is a security scheme type in swagger 2, in openapi 3
+    it is a scheme of the http security scheme type
+    */
+    let (r#type, scheme) = match security_scheme_node.r#type() {
+      Some("basic") => ("http".to_owned(), Some("basic".to_owned())),
+      r#type => (r#type.map(Into::into).unwrap_or_default(), None),
+    };
+
+    Ok(models::Authentication {
+      location: security_scheme_location.clone(),
+      name,
+      r#in: security_scheme_node.r#in().map(Into::into),
+      description: security_scheme_node.description().map(Into::into),
+      scheme,
+      r#type,
+      parameter_name: security_scheme_node.parameter_name().map(Into::into),
+    })
+  }
+
+  fn make_authentication_requirement_group(
+    &self,
+    requirements: BTreeMap<String, Vec<String>>,
+  ) -> AuthenticationRequirementGroup {
+    AuthenticationRequirementGroup {
+      requirements: requirements
+        .into_iter()
+        .map(|(name, scopes)| self.make_authentication_requirement(name, scopes))
+        .map(rc::Rc::new)
+        .collect(),
+    }
+  }
+
+  fn make_authentication_requirement(
+    &self,
+    name: String,
+    scopes: Vec<String>,
+  ) -> AuthenticationRequirement {
+    AuthenticationRequirement {
+      authentication_name: name,
+      scopes,
+    }
+  }
+}
+
+impl Document {
+  fn get_referenced_locations_from_api(
+    &self,
+    location: NodeLocation,
+    node: nodes::Api,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    Self::get_sub_locations_from_node_entries(
+      location,
+      node.paths().into_iter().flatten(),
+      |location, node| self.get_referenced_locations_from_path(location, node),
+    )
+  }
+
+  fn get_referenced_locations_from_path(
+    &self,
+    location: NodeLocation,
+    node: nodes::Path,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    let reference_location = node
+      .reference()
+      .map(|reference| reference.parse().map_err(Into::into));
+
+    iter::empty()
+      .chain(reference_location)
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.operations().into_iter().flatten(),
+        |location, node| self.get_referenced_locations_from_operation(location, node),
+      ))
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.request_parameters().into_iter().flatten(),
+      ))
+  }
+
+  fn get_referenced_locations_from_operation(
+    &self,
+    location: NodeLocation,
+    node: nodes::Operation,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.operation_results().into_iter().flatten(),
+      ))
+      .chain(Self::get_referenced_locations_from_reference_entries(
+        location.clone(),
+        node.request_parameters().into_iter().flatten(),
+      ))
+  }
+}
+
+impl Document {
+  fn get_schema_locations_from_api(
+    &self,
+    location: NodeLocation,
+    node: nodes::Api,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain({
+        let location = location.clone();
+        node
+          .schema_definition_pointers()
+          .into_iter()
+          .flatten()
+          .map(move |pointer| Ok(location.push_pointer(pointer)))
+      })
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.parameter_definitions().into_iter().flatten(),
+        |location, node| self.get_schema_locations_from_request_parameter(location, node),
+      ))
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.response_definitions().into_iter().flatten(),
+        |location, node| self.get_schema_locations_from_operation_result(location, node),
+      ))
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.paths().into_iter().flatten(),
+        |location, node| self.get_schema_locations_from_path(location, node),
+      ))
+  }
+
+  fn get_schema_locations_from_path(
+    &self,
+    location: NodeLocation,
+    node: nodes::Path,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .request_parameters()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_request_parameter(location, node),
+      ))
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node.operations().into_iter().flatten(),
+        |location, node| self.get_schema_locations_from_operation(location, node),
+      ))
+  }
+
+  fn get_schema_locations_from_operation(
+    &self,
+    location: NodeLocation,
+    node: nodes::Operation,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    iter::empty()
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .request_parameters()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| self.get_schema_locations_from_request_parameter(location, node),
+      ))
+      .chain(Self::get_sub_locations_from_node_entries(
+        location.clone(),
+        node
+          .operation_results()
+          .into_iter()
+          .flatten()
+          .filter_map(|(pointer, node)| node.into_node().map(|node| (pointer, node))),
+        |location, node| {
+          self
+            .get_schema_locations_from_operation_result(location, node)
+            .collect::<Vec<_>>()
+            .into_iter()
+        },
+      ))
+  }
+
+  fn get_schema_locations_from_operation_result(
+    &self,
+    location: NodeLocation,
+    node: nodes::OperationResult,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> + '_ {
+    node
+      .schema_pointer()
+      .into_iter()
+      .map(move |pointer| location.push_pointer(pointer))
+      .map(Ok)
+  }
+
+  fn get_schema_locations_from_request_parameter(
+    &self,
+    location: NodeLocation,
+    node: nodes::RequestParameter,
+  ) -> impl Iterator<Item = Result<NodeLocation, DocumentError>> {
+    node
+      .schema_pointer()
+      .into_iter()
+      .map(move |pointer| location.push_pointer(pointer))
+      .map(Ok)
+  }
+}
+
+impl Document {
+  /**
+  derived schemas are found the same way the api model is made, so there is
+  a derived schema for every schema id of a (non body) parameter, a header or
+  a form in the model. Paths and operations that fail are skipped here, they
+  are reported when the api model is made.
+  */
+  fn get_derived_schemas_from_api(
+    &self,
+    location: NodeLocation,
+    node: nodes::Api,
+  ) -> Result<Vec<(NodeLocation, serde_json::Value)>, DocumentError> {
+    let mut derived_schemas = BTreeMap::new();
+
+    for (pointer, parameter_node) in node.parameter_definitions().into_iter().flatten() {
+      if let Some(schema) = parameter_node.schema() {
+        derived_schemas.insert(location.push_pointer(pointer), schema);
+      }
+    }
+
+    for (pointer, operation_result_node) in node.response_definitions().into_iter().flatten() {
+      derived_schemas.extend(Self::get_derived_schemas_from_operation_result(
+        location.push_pointer(pointer),
+        operation_result_node,
+      ));
+    }
+
+    for (pointer, path_node) in node.paths().into_iter().flatten() {
+      if pointer.last().unwrap().starts_with("x-") {
+        continue;
+      }
+
+      let path_location = location.push_pointer(pointer);
+      let Ok(path_layers) = self.resolve_path(&path_location, path_node) else {
+        continue;
+      };
+      let Ok(path_parameter_nodes) = self.get_path_parameter_nodes(&path_layers) else {
+        continue;
+      };
+
+      for (operation_location, operation_node) in
+        Self::get_operation_nodes(&path_layers).into_values()
+      {
+        let Ok(parameter_nodes) = self.get_operation_parameter_nodes(
+          &path_parameter_nodes,
+          &operation_location,
+          &operation_node,
+        ) else {
+          continue;
+        };
+
+        for (parameter_location, parameter_node) in &parameter_nodes {
+          if let Some(schema) = parameter_node.schema() {
+            derived_schemas.insert(parameter_location.clone(), schema);
+          }
+        }
+
+        if let Ok(Some(schema)) = self.make_form_data_schema(&parameter_nodes) {
+          derived_schemas.insert(operation_location.clone(), schema);
+        }
+
+        for (pointer, operation_result_node) in
+          operation_node.operation_results().into_iter().flatten()
+        {
+          let Ok((operation_result_location, operation_result_node)) = self.dereference(
+            &operation_location.push_pointer(pointer),
+            operation_result_node,
+          ) else {
+            continue;
+          };
+          derived_schemas.extend(Self::get_derived_schemas_from_operation_result(
+            operation_result_location,
+            operation_result_node,
+          ));
+        }
+      }
+    }
+
+    Ok(derived_schemas.into_iter().collect())
+  }
+
+  fn get_derived_schemas_from_operation_result(
+    location: NodeLocation,
+    node: nodes::OperationResult,
+  ) -> impl Iterator<Item = (NodeLocation, serde_json::Value)> {
+    node
+      .response_headers()
+      .into_iter()
+      .flatten()
+      .filter_map(move |(pointer, header_node)| {
+        Some((location.push_pointer(pointer), header_node.schema()?))
+      })
   }
 }
@@ -0,0 +1,22 @@
+mod api;
+mod items;
+mod operation;
+mod operation_result;
+mod path;
+mod request_parameter;
+mod response_header;
+mod security_scheme;
+
+pub use api::*;
+pub use items::*;
+pub use operation::*;
+pub use operation_result::*;
+pub use path::*;
+pub use request_parameter::*;
+pub use response_header::*;
+pub use security_scheme::*;
+
+/*
+references are the same as in 3.0
+*/
+pub use crate::documents::oas30::nodes::NodeOrReference;
@@ -0,0 +1,160 @@
+use super::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone)]
+pub struct Api(serde_json::Value);
+
+impl Api {
+  pub fn host(&self) -> Option<&str> {
+    self.0.as_object()?.get("host")?.as_str()
+  }
+
+  pub fn base_path(&self) -> Option<&str> {
+    self.0.as_object()?.get("basePath")?.as_str()
+  }
+
+  pub fn schemes(&self) -> Option<Vec<String>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .get("schemes")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| Some(value.as_str()?.to_owned()))
+        .collect(),
+    )
+  }
+
+  pub fn paths(&self) -> Option<BTreeMap<Vec<String>, Path>> {
+    let member = "paths";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn security_schemes(&self) -> Option<BTreeMap<Vec<String>, SecurityScheme>> {
+    let member = "securityDefinitions";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn security(&self) -> Option<Vec<BTreeMap<String, Vec<String>>>> {
+    let member = "security";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_array()?
+        .iter()
+        .filter_map(|value| {
+          Some(
+            value
+              .as_object()?
+              .iter()
+              .filter_map(|(key, value)| {
+                Some((
+                  key.to_owned(),
+                  value
+                    .as_array()?
+                    .iter()
+                    .filter_map(|value| Some(value.as_str()?.to_owned()))
+                    .collect(),
+                ))
+              })
+              .collect(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn consumes(&self) -> Option<Vec<String>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .get("consumes")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| Some(value.as_str()?.to_owned()))
+        .collect(),
+    )
+  }
+
+  pub fn produces(&self) -> Option<Vec<String>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .get("produces")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| Some(value.as_str()?.to_owned()))
+        .collect(),
+    )
+  }
+
+  pub fn schema_definition_pointers(&self) -> Option<BTreeSet<Vec<String>>> {
+    Some(
+      self
+        .0
+        .as_object()?
+        .get("definitions")?
+        .as_object()?
+        .keys()
+        .map(|key| vec!["definitions".to_owned(), key.to_owned()])
+        .collect(),
+    )
+  }
+
+  pub fn parameter_definitions(&self) -> Option<BTreeMap<Vec<String>, RequestParameter>> {
+    let member = "parameters";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+
+  pub fn response_definitions(&self) -> Option<BTreeMap<Vec<String>, OperationResult>> {
+    let member = "responses";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| (vec![member.to_owned(), key.clone()], node.clone().into()))
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for Api {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
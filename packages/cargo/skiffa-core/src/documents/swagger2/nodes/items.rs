@@ -0,0 +1,47 @@
+/**
+the keywords of a parameter, header or items object that are schema keywords
+*/
+const SCHEMA_MEMBERS: [&str; 16] = [
+  "type",
+  "format",
+  "items",
+  "default",
+  "maximum",
+  "exclusiveMaximum",
+  "minimum",
+  "exclusiveMinimum",
+  "maxLength",
+  "minLength",
+  "pattern",
+  "maxItems",
+  "minItems",
+  "uniqueItems",
+  "enum",
+  "multipleOf",
+];
+
+/// Parameters (other than body parameters), headers and items are not a
+/// schema, but they have the keywords of one next to keywords like `in`,
+/// `name`, `required` and `collectionFormat`. This returns a schema with
+/// only the schema keywords, a file is a binary string.
+///
+pub fn make_items_schema(node: &serde_json::Value) -> serde_json::Value {
+  let mut schema = serde_json::Map::new();
+  for (member, member_node) in node.as_object().into_iter().flatten() {
+    match member.as_str() {
+      "items" => {
+        schema.insert(member.clone(), make_items_schema(member_node));
+      }
+      "type" if member_node.as_str() == Some("file") => {
+        schema.insert("type".to_owned(), "string".into());
+        schema.insert("format".to_owned(), "binary".into());
+      }
+      member if SCHEMA_MEMBERS.contains(&member) => {
+        schema.insert(member.to_owned(), member_node.clone());
+      }
+      _ => {}
+    }
+  }
+
+  schema.into()
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct OperationResult(serde_json::Value);
+
+impl OperationResult {
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn schema_pointer(&self) -> Option<Vec<String>> {
+    self
+      .0
+      .as_object()?
+      .get("schema")
+      .map(|_value| vec!["schema".to_owned()])
+  }
+
+  pub fn response_headers(&self) -> Option<BTreeMap<Vec<String>, ResponseHeader>> {
+    let member = "headers";
+    Some(
+      self
+        .0
+        .as_object()?
+        .get(member)?
+        .as_object()?
+        .into_iter()
+        .map(|(key, node)| {
+          (
+            vec![member.to_owned(), key.to_string()],
+            node.clone().into(),
+          )
+        })
+        .collect(),
+    )
+  }
+}
+
+impl From<serde_json::Value> for OperationResult {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+#[derive(Clone)]
+pub struct RequestParameter(serde_json::Value);
+
+impl RequestParameter {
+  /// Body parameters have a schema, the pointer to it is returned. Other
+  /// parameters have no schema, see `schema`.
+  ///
+  pub fn schema_pointer(&self) -> Option<Vec<String>> {
+    if self.r#in()? != "body" {
+      return None;
+    }
+
+    self
+      .0
+      .as_object()?
+      .get("schema")
+      .map(|_value| vec!["schema".to_owned()])
+  }
+
+  /// Parameters other than body parameters have the keywords of a schema,
+  /// this returns a schema derived from those keywords.
+  ///
+  pub fn schema(&self) -> Option<serde_json::Value> {
+    if self.r#in()? == "body" {
+      return None;
+    }
+
+    self.0.as_object()?.get("type")?;
+    Some(make_items_schema(&self.0))
+  }
+
+  pub fn r#in(&self) -> Option<&str> {
+    self.0.as_object()?.get("in")?.as_str()
+  }
+
+  pub fn name(&self) -> Option<&str> {
+    self.0.as_object()?.get("name")?.as_str()
+  }
+
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn collection_format(&self) -> Option<&str> {
+    self.0.as_object()?.get("collectionFormat")?.as_str()
+  }
+
+  pub fn allow_empty_value(&self) -> Option<bool> {
+    self.0.as_object()?.get("allowEmptyValue")?.as_bool()
+  }
+
+  pub fn required(&self) -> Option<bool> {
+    self.0.as_object()?.get("required")?.as_bool()
+  }
+}
+
+impl From<serde_json::Value> for RequestParameter {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
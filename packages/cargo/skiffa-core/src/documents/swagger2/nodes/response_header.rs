@@ -0,0 +1,28 @@
+use super::*;
+
+#[derive(Clone)]
+pub struct ResponseHeader(serde_json::Value);
+
+impl ResponseHeader {
+  pub fn description(&self) -> Option<&str> {
+    self.0.as_object()?.get("description")?.as_str()
+  }
+
+  pub fn collection_format(&self) -> Option<&str> {
+    self.0.as_object()?.get("collectionFormat")?.as_str()
+  }
+
+  /// A header has the keywords of a schema, this returns a schema derived
+  /// from those keywords.
+  ///
+  pub fn schema(&self) -> Option<serde_json::Value> {
+    self.0.as_object()?.get("type")?;
+    Some(make_items_schema(&self.0))
+  }
+}
+
+impl From<serde_json::Value> for ResponseHeader {
+  fn from(value: serde_json::Value) -> Self {
+    Self(value)
+  }
+}
@@ -18,10 +18,10 @@ impl TryFrom<&serde_json::Value> for DocumentType {
     let document = value.as_object().ok_or(DocumentTypeError)?;
 
     if let Some(version) = document.get("swagger") {
+      // the swagger version is always "2.0", that is not a valid semver
       let version = version.as_str().ok_or(DocumentTypeError)?;
-      let version = Version::parse(version).map_err(|_error| DocumentTypeError)?;
 
-      if version.major == 2 && version.minor == 0 {
+      if version == "2.0" {
         return Ok(Self::Swagger2);
       }
     }
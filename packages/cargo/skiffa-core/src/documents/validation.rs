@@ -0,0 +1,57 @@
+use super::{Diagnostic, DiagnosticSeverity};
+use crate::error::Error;
+use crate::utils::NodeLocation;
+
+/*
+messages of validation errors contain the offending node, which may be a
+very large node, like a path item
+*/
+const MAXIMUM_MESSAGE_LENGTH: usize = 200;
+
+/// Makes a validator for a meta-schema that is bundled with this crate. The
+/// bundled meta-schemas only reference the json schema meta-schemas, those
+/// are bundled with the validator so no network is needed.
+///
+pub fn make_meta_schema_validator(meta_schema: &str) -> jsonschema::Validator {
+  let meta_schema = serde_json::from_str(meta_schema).unwrap();
+  jsonschema::options()
+    // formats are not validated, server urls for instance are templates
+    .should_validate_formats(false)
+    .build(&meta_schema)
+    .unwrap()
+}
+
+/// Validates a document node against a meta-schema, every violation is
+/// returned as a diagnostic located at the offending node.
+///
+pub fn validate_node(
+  validator: &jsonschema::Validator,
+  location: &NodeLocation,
+  node: &serde_json::Value,
+) -> Vec<Diagnostic> {
+  validator
+    .iter_errors(node)
+    .map(|error| {
+      let pointer = error
+        .instance_path
+        .as_str()
+        .split('/')
+        .skip(1)
+        .map(|member| member.replace("~1", "/").replace("~0", "~"))
+        .collect();
+
+      let mut message = error.to_string();
+      if let Some((index, _)) = message.char_indices().nth(MAXIMUM_MESSAGE_LENGTH) {
+        message.truncate(index);
+        message.push_str("...");
+      }
+
+      Diagnostic {
+        location: location.push_pointer(pointer),
+        message,
+        severity: DiagnosticSeverity::Error,
+        cause: Some(Error::ValidationFailed),
+      }
+    })
+    .collect()
+}
@@ -1,5 +1,5 @@
 use crate::documents::{DocumentError, DocumentTypeError};
-use crate::utils::{NodeCacheError, ParseLocationError};
+use crate::utils::{NodeCacheError, ParseJsonPathError, ParseLocationError};
 use std::fmt::Display;
 use wasm_bindgen::prelude::*;
 
@@ -12,9 +12,17 @@ pub enum Error {
   ParseLocationFailed,
   ParseMethodFailed,
   ParseStatusKindFailed,
+  ParseParameterStyleFailed,
   DocumentTypeError,
   FetchError,
   SerializationError,
+  ValidationFailed,
+  ReferenceCycle,
+  MissingField,
+  ContextUnavailable,
+  LockfileMismatch,
+  FetchPolicyViolation,
+  ParseJsonPathFailed,
 }
 
 impl std::error::Error for Error {}
@@ -28,9 +36,17 @@ impl Display for Error {
       Self::ParseLocationFailed => write!(f, "ParseLocationFailed"),
       Self::ParseMethodFailed => write!(f, "ParseMethodFailed"),
       Self::ParseStatusKindFailed => write!(f, "ParseStatusKindFailed"),
+      Self::ParseParameterStyleFailed => write!(f, "ParseParameterStyleFailed"),
       Self::DocumentTypeError => write!(f, "DocumentTypeError"),
       Self::FetchError => write!(f, "FetchError"),
       Self::SerializationError => write!(f, "SerializationError"),
+      Self::ValidationFailed => write!(f, "ValidationFailed"),
+      Self::ReferenceCycle => write!(f, "ReferenceCycle"),
+      Self::MissingField => write!(f, "MissingField"),
+      Self::ContextUnavailable => write!(f, "ContextUnavailable"),
+      Self::LockfileMismatch => write!(f, "LockfileMismatch"),
+      Self::FetchPolicyViolation => write!(f, "FetchPolicyViolation"),
+      Self::ParseJsonPathFailed => write!(f, "ParseJsonPathFailed"),
     }
   }
   //
@@ -42,6 +58,12 @@ impl From<ParseLocationError> for Error {
   }
 }
 
+impl From<ParseJsonPathError> for Error {
+  fn from(_value: ParseJsonPathError) -> Self {
+    Self::ParseJsonPathFailed
+  }
+}
+
 impl From<NodeCacheError> for Error {
   fn from(value: NodeCacheError) -> Self {
     match value {
@@ -66,6 +88,10 @@ impl From<DocumentError> for Error {
       DocumentError::ParseLocationFailed => Self::ParseLocationFailed,
       DocumentError::ParseMethodFailed => Self::ParseMethodFailed,
       DocumentError::ParseStatusKindFailed => Self::ParseStatusKindFailed,
+      DocumentError::ParseParameterStyleFailed => Self::ParseParameterStyleFailed,
+      DocumentError::ReferenceCycle => Self::ReferenceCycle,
+      DocumentError::MissingField => Self::MissingField,
+      DocumentError::ContextUnavailable => Self::ContextUnavailable,
     }
   }
 }
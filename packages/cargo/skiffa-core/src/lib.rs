@@ -1,4 +1,6 @@
 pub mod documents;
 pub mod error;
+pub mod lint;
 pub mod models;
+pub mod transform;
 pub mod utils;
@@ -0,0 +1,5 @@
+mod rule;
+mod rules;
+
+pub use rule::*;
+pub use rules::*;
@@ -0,0 +1,61 @@
+use crate::documents::{DiagnosticSeverity, DocumentContext};
+use crate::models;
+use crate::utils::{JsonPath, NodeLocation};
+
+/// A lint rule checks an api for a convention. Every violation is reported as
+/// a diagnostic with the severity that is configured for the rule.
+///
+pub trait Rule {
+  /// Unique name of the rule, used to configure the rule
+  fn name(&self) -> &'static str;
+  /// Severity of the violations if no severity is configured
+  fn default_severity(&self) -> DiagnosticSeverity {
+    DiagnosticSeverity::Warning
+  }
+  fn check(&self, context: &RuleContext) -> Vec<RuleViolation>;
+}
+
+pub struct RuleViolation {
+  pub location: NodeLocation,
+  pub message: String,
+}
+
+/// What a rule can check, the api model and the raw nodes of all loaded
+/// documents.
+///
+pub struct RuleContext<'a> {
+  pub document_location: &'a NodeLocation,
+  pub api: &'a models::Api,
+  context: &'a DocumentContext,
+}
+
+impl<'a> RuleContext<'a> {
+  pub fn new(
+    document_location: &'a NodeLocation,
+    api: &'a models::Api,
+    context: &'a DocumentContext,
+  ) -> Self {
+    Self {
+      document_location,
+      api,
+      context,
+    }
+  }
+
+  pub fn get_node(&self, location: &NodeLocation) -> Option<serde_json::Value> {
+    self.context.get_node(location)
+  }
+
+  /// Locations of the nodes selected by the JSONPath query, from the node at
+  /// the location
+  ///
+  pub fn query(&self, location: &NodeLocation, path: &JsonPath) -> Vec<NodeLocation> {
+    self.context.query(location, path)
+  }
+
+  /// Locations of all loaded documents, including the linted document
+  ///
+  pub fn get_document_locations(&self) -> Vec<NodeLocation> {
+    self.context.get_document_locations()
+  }
+}
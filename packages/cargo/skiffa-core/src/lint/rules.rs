@@ -0,0 +1,25 @@
+mod kebab_case_paths;
+mod operation_4xx_response;
+mod operation_id;
+mod security_scheme_description;
+mod unused_components;
+
+pub use kebab_case_paths::*;
+pub use operation_4xx_response::*;
+pub use operation_id::*;
+pub use security_scheme_description::*;
+pub use unused_components::*;
+
+use super::Rule;
+
+/// The built in ruleset
+///
+pub fn well_known_rules() -> Vec<Box<dyn Rule>> {
+  vec![
+    Box::new(KebabCasePathsRule),
+    Box::new(OperationIdRule),
+    Box::new(Operation4xxResponseRule),
+    Box::new(UnusedComponentsRule),
+    Box::new(SecuritySchemeDescriptionRule),
+  ]
+}
@@ -0,0 +1,36 @@
+use crate::lint::{Rule, RuleContext, RuleViolation};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static KEBAB_CASE_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap());
+
+/// Every segment of a path pattern, except for parameters, is kebab-case.
+///
+pub struct KebabCasePathsRule;
+
+impl Rule for KebabCasePathsRule {
+  fn name(&self) -> &'static str {
+    "kebab-case-paths"
+  }
+
+  fn check(&self, context: &RuleContext) -> Vec<RuleViolation> {
+    context
+      .api
+      .paths
+      .iter()
+      .filter(|path| {
+        path
+          .pattern
+          .split('/')
+          .filter(|segment| !segment.is_empty())
+          .filter(|segment| !(segment.starts_with('{') && segment.ends_with('}')))
+          .any(|segment| !KEBAB_CASE_REGEX.is_match(segment))
+      })
+      .map(|path| RuleViolation {
+        location: path.location.clone(),
+        message: format!("path {} is not kebab-case", path.pattern),
+      })
+      .collect()
+  }
+}
@@ -0,0 +1,41 @@
+use crate::lint::{Rule, RuleContext, RuleViolation};
+use crate::models::StatusKind;
+
+/// Every operation has a response for a client error (4XX). The default
+/// response does not count.
+///
+pub struct Operation4xxResponseRule;
+
+impl Rule for Operation4xxResponseRule {
+  fn name(&self) -> &'static str {
+    "operation-4xx-response"
+  }
+
+  fn check(&self, context: &RuleContext) -> Vec<RuleViolation> {
+    let api = context.api;
+    api
+      .paths
+      .iter()
+      .flat_map(|path| path.operations.iter())
+      .chain(
+        api
+          .webhooks
+          .iter()
+          .flat_map(|webhook| webhook.operations.iter()),
+      )
+      .filter(|operation| {
+        !operation.operation_results.iter().any(|operation_result| {
+          operation_result.status_kind != StatusKind::Default
+            && operation_result
+              .status_codes
+              .iter()
+              .any(|status_code| (400..500).contains(status_code))
+        })
+      })
+      .map(|operation| RuleViolation {
+        location: operation.location.clone(),
+        message: format!("operation {} has no 4XX response", operation.name),
+      })
+      .collect()
+  }
+}
@@ -0,0 +1,34 @@
+use crate::lint::{Rule, RuleContext, RuleViolation};
+
+/// Every operation has an operationId.
+///
+pub struct OperationIdRule;
+
+impl Rule for OperationIdRule {
+  fn name(&self) -> &'static str {
+    "operation-id"
+  }
+
+  fn check(&self, context: &RuleContext) -> Vec<RuleViolation> {
+    let api = context.api;
+    api
+      .paths
+      .iter()
+      .flat_map(|path| path.operations.iter())
+      .chain(
+        api
+          .webhooks
+          .iter()
+          .flat_map(|webhook| webhook.operations.iter()),
+      )
+      .filter(|operation| operation.operation_id.is_none())
+      .map(|operation| RuleViolation {
+        location: operation.location.clone(),
+        message: format!(
+          "operation has no operationId, the name {} is synthesized",
+          operation.name
+        ),
+      })
+      .collect()
+  }
+}
@@ -0,0 +1,30 @@
+use crate::lint::{Rule, RuleContext, RuleViolation};
+
+/// Every security scheme has a description.
+///
+pub struct SecuritySchemeDescriptionRule;
+
+impl Rule for SecuritySchemeDescriptionRule {
+  fn name(&self) -> &'static str {
+    "security-scheme-description"
+  }
+
+  fn check(&self, context: &RuleContext) -> Vec<RuleViolation> {
+    context
+      .api
+      .authentication
+      .iter()
+      .filter(|authentication| {
+        authentication
+          .description
+          .as_ref()
+          .map(|description| description.trim().is_empty())
+          .unwrap_or(true)
+      })
+      .map(|authentication| RuleViolation {
+        location: authentication.location.clone(),
+        message: format!("security scheme {} has no description", authentication.name),
+      })
+      .collect()
+  }
+}
@@ -0,0 +1,127 @@
+use crate::lint::{Rule, RuleContext, RuleViolation};
+use crate::utils::find_node_references;
+use std::collections::BTreeSet;
+
+/// Every component is used. A component is used when it is referenced from
+/// any of the loaded documents, references from within the component itself
+/// do not count. Security schemes are used when an operation requires them.
+///
+pub struct UnusedComponentsRule;
+
+impl Rule for UnusedComponentsRule {
+  fn name(&self) -> &'static str {
+    "unused-components"
+  }
+
+  fn check(&self, context: &RuleContext) -> Vec<RuleViolation> {
+    let document_location = context.document_location;
+    let Some(document_node) = context.get_node(document_location) else {
+      return Vec::new();
+    };
+
+    /*
+    sections with components that are referenced, and the section with the
+    security schemes
+    */
+    let (section_pointers, security_scheme_section_pointer) =
+      if document_node.get("swagger").is_some() {
+        (
+          vec![
+            vec!["definitions".to_owned()],
+            vec!["parameters".to_owned()],
+            vec!["responses".to_owned()],
+          ],
+          vec!["securityDefinitions".to_owned()],
+        )
+      } else {
+        (
+          document_node
+            .get("components")
+            .and_then(|node| node.as_object())
+            .into_iter()
+            .flat_map(|node| node.keys())
+            .filter(|key| *key != "securitySchemes" && !key.starts_with("x-"))
+            .map(|key| vec!["components".to_owned(), key.clone()])
+            .collect(),
+          vec!["components".to_owned(), "securitySchemes".to_owned()],
+        )
+      };
+
+    let references: Vec<_> = context
+      .get_document_locations()
+      .into_iter()
+      .flat_map(|location| {
+        let node = context.get_node(&location).unwrap_or_default();
+        find_node_references(&location, &node)
+      })
+      .collect();
+
+    let used_security_scheme_names: BTreeSet<_> = context
+      .api
+      .paths
+      .iter()
+      .flat_map(|path| path.operations.iter())
+      .chain(
+        context
+          .api
+          .webhooks
+          .iter()
+          .flat_map(|webhook| webhook.operations.iter()),
+      )
+      .flat_map(|operation| operation.authentication_requirements.iter())
+      .flat_map(|group| group.requirements.iter())
+      .map(|requirement| requirement.authentication_name.clone())
+      .collect();
+
+    let mut violations = Vec::new();
+
+    for section_pointer in section_pointers {
+      for name in get_member_names(&document_node, &section_pointer) {
+        let pointer: Vec<_> = section_pointer
+          .iter()
+          .cloned()
+          .chain([name.clone()])
+          .collect();
+        let location = document_location.push_pointer(pointer);
+        let used = references.iter().any(|reference| {
+          location.contains(&reference.target_location)
+            && !location.contains(&reference.source_location)
+        });
+        if !used {
+          violations.push(RuleViolation {
+            location,
+            message: format!("component {} is not used", name),
+          });
+        }
+      }
+    }
+
+    for name in get_member_names(&document_node, &security_scheme_section_pointer) {
+      if !used_security_scheme_names.contains(&name) {
+        let pointer: Vec<_> = security_scheme_section_pointer
+          .iter()
+          .cloned()
+          .chain([name.clone()])
+          .collect();
+        violations.push(RuleViolation {
+          location: document_location.push_pointer(pointer),
+          message: format!("security scheme {} is not used", name),
+        });
+      }
+    }
+
+    violations
+  }
+}
+
+fn get_member_names(node: &serde_json::Value, pointer: &[String]) -> Vec<String> {
+  pointer
+    .iter()
+    .try_fold(node, |node, member| node.get(member))
+    .and_then(|node| node.as_object())
+    .into_iter()
+    .flat_map(|node| node.keys())
+    .filter(|key| !key.starts_with("x-"))
+    .cloned()
+    .collect()
+}
@@ -5,10 +5,15 @@ mod authentication_requirement_group;
 mod body;
 mod method;
 mod operation;
+mod operation_name_strategy;
 mod operation_result;
 mod parameter;
+mod parameter_style;
 mod path;
+mod server;
+mod server_variable;
 mod status_kind;
+mod webhook;
 
 pub use api::*;
 pub use authentication::*;
@@ -17,7 +22,12 @@ pub use authentication_requirement_group::*;
 pub use body::*;
 pub use method::*;
 pub use operation::*;
+pub use operation_name_strategy::*;
 pub use operation_result::*;
 pub use parameter::*;
+pub use parameter_style::*;
 pub use path::*;
+pub use server::*;
+pub use server_variable::*;
 pub use status_kind::*;
+pub use webhook::*;
@@ -1,11 +1,17 @@
-use super::{Authentication, AuthenticationContainer, Path, PathContainer};
+use super::{
+  Authentication, AuthenticationContainer, Path, PathContainer, Server, ServerContainer, Webhook,
+  WebhookContainer,
+};
 use crate::utils::NodeLocation;
 use std::rc;
 use wasm_bindgen::prelude::*;
 
+#[derive(Clone)]
 pub struct Api {
   pub location: NodeLocation,
+  pub servers: Vec<rc::Rc<Server>>,
   pub paths: Vec<rc::Rc<Path>>,
+  pub webhooks: Vec<rc::Rc<Webhook>>,
   pub authentication: Vec<rc::Rc<Authentication>>,
 }
 
@@ -20,6 +26,17 @@ impl ApiContainer {
     self.0.location.to_string()
   }
 
+  #[wasm_bindgen(getter, js_name = "servers")]
+  pub fn servers(&self) -> Vec<ServerContainer> {
+    self
+      .0
+      .servers
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
   #[wasm_bindgen(getter, js_name = "paths")]
   pub fn paths(&self) -> Vec<PathContainer> {
     self
@@ -31,6 +48,17 @@ impl ApiContainer {
       .collect()
   }
 
+  #[wasm_bindgen(getter, js_name = "webhooks")]
+  pub fn webhooks(&self) -> Vec<WebhookContainer> {
+    self
+      .0
+      .webhooks
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
   #[wasm_bindgen(getter, js_name = "authentication")]
   pub fn authentication(&self) -> Vec<AuthenticationContainer> {
     self
@@ -1,19 +1,28 @@
 use super::{
   AuthenticationRequirementGroup, AuthenticationRequirementGroupContainer, Body, BodyContainer,
-  Method, OperationResult, OperationResultContainer, Parameter, ParameterContainer,
+  Method, OperationResult, OperationResultContainer, Parameter, ParameterContainer, Server,
+  ServerContainer,
 };
 use crate::utils::NodeLocation;
 use std::rc;
 use wasm_bindgen::prelude::*;
 
+#[derive(Clone)]
 pub struct Operation {
   pub location: NodeLocation,
   pub method: Method,
+  /// the operationId, or a synthesized name if the operation has no operationId
   pub name: String,
+  pub operation_id: Option<String>,
   pub summary: Option<String>,
   pub description: Option<String>,
   pub deprecated: bool,
+  /// servers of the operation, inherited from the path if the operation does not define any
+  pub servers: Vec<rc::Rc<Server>>,
   pub authentication_requirements: Vec<rc::Rc<AuthenticationRequirementGroup>>,
+  /// true if the request body is required
+  pub body_required: bool,
+  pub body_description: Option<String>,
   pub query_parameters: Vec<rc::Rc<Parameter>>,
   pub header_parameters: Vec<rc::Rc<Parameter>>,
   pub path_parameters: Vec<rc::Rc<Parameter>>,
@@ -43,6 +52,11 @@ impl OperationContainer {
     self.0.name.clone()
   }
 
+  #[wasm_bindgen(getter, js_name = "operationId")]
+  pub fn operation_id(&self) -> Option<String> {
+    self.0.operation_id.clone()
+  }
+
   #[wasm_bindgen(getter, js_name = "summary")]
   pub fn summary(&self) -> Option<String> {
     self.0.summary.clone()
@@ -58,6 +72,17 @@ impl OperationContainer {
     self.0.deprecated
   }
 
+  #[wasm_bindgen(getter, js_name = "servers")]
+  pub fn servers(&self) -> Vec<ServerContainer> {
+    self
+      .0
+      .servers
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
   #[wasm_bindgen(getter, js_name = "authenticationRequirements")]
   pub fn authentication_requirements(&self) -> Vec<AuthenticationRequirementGroupContainer> {
     self
@@ -124,6 +149,16 @@ impl OperationContainer {
       .collect()
   }
 
+  #[wasm_bindgen(getter, js_name = "bodyRequired")]
+  pub fn body_required(&self) -> bool {
+    self.0.body_required
+  }
+
+  #[wasm_bindgen(getter, js_name = "bodyDescription")]
+  pub fn body_description(&self) -> Option<String> {
+    self.0.body_description.clone()
+  }
+
   #[wasm_bindgen(getter, js_name = "operationResults")]
   pub fn operation_results(&self) -> Vec<OperationResultContainer> {
     self
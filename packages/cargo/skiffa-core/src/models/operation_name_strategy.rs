@@ -0,0 +1,112 @@
+use super::Method;
+use wasm_bindgen::prelude::*;
+
+/// How to name operations that have no operationId. The name is made from the
+/// method and the path pattern (or the webhook name).
+///
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OperationNameStrategy {
+  /// `get /users/{id}` becomes `getUsersById`
+  #[default]
+  MethodPath,
+  /// `get /users/{id}` becomes `usersByIdGet`
+  PathMethod,
+}
+
+impl OperationNameStrategy {
+  pub fn make_operation_name(&self, method: Method, pattern: &str) -> String {
+    let method = method.to_string();
+    let path_words = pattern
+      .split('/')
+      .filter(|segment| !segment.is_empty())
+      .flat_map(|segment| {
+        /*
+        parameters in the path are prefixed with by, so /users/{id} becomes
+        users by id
+        */
+        let prefix = if segment.starts_with('{') && segment.ends_with('}') {
+          Some("by")
+        } else {
+          None
+        };
+        prefix.into_iter().chain(
+          segment
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty()),
+        )
+      });
+
+    let words: Vec<_> = match self {
+      Self::MethodPath => std::iter::once(method.as_str()).chain(path_words).collect(),
+      Self::PathMethod => path_words.chain(std::iter::once(method.as_str())).collect(),
+    };
+
+    words
+      .into_iter()
+      .enumerate()
+      .map(|(index, word)| {
+        if index == 0 {
+          to_lower_first(word)
+        } else {
+          to_upper_first(word)
+        }
+      })
+      .collect()
+  }
+}
+
+fn to_upper_first(word: &str) -> String {
+  let mut chars = word.chars();
+  chars
+    .next()
+    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+    .unwrap_or_default()
+}
+
+fn to_lower_first(word: &str) -> String {
+  let mut chars = word.chars();
+  chars
+    .next()
+    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_make_operation_name() {
+    do_test(
+      OperationNameStrategy::MethodPath,
+      Method::Get,
+      "/users/{id}",
+      "getUsersById",
+    );
+    do_test(
+      OperationNameStrategy::PathMethod,
+      Method::Get,
+      "/users/{id}",
+      "usersByIdGet",
+    );
+    do_test(
+      OperationNameStrategy::MethodPath,
+      Method::Post,
+      "/user-groups/{group_id}/members.json",
+      "postUserGroupsByGroupIdMembersJson",
+    );
+    do_test(OperationNameStrategy::MethodPath, Method::Get, "/", "get");
+    do_test(
+      OperationNameStrategy::MethodPath,
+      Method::Post,
+      "newPet",
+      "postNewPet",
+    );
+
+    fn do_test(strategy: OperationNameStrategy, method: Method, pattern: &str, expected: &str) {
+      let actual = strategy.make_operation_name(method, pattern);
+      assert_eq!(actual, expected);
+    }
+  }
+}
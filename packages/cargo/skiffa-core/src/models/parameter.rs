@@ -1,3 +1,4 @@
+use super::ParameterStyle;
 use crate::utils::NodeLocation;
 use std::rc;
 use wasm_bindgen::prelude::*;
@@ -5,7 +6,16 @@ use wasm_bindgen::prelude::*;
 pub struct Parameter {
   pub location: NodeLocation,
   pub name: String,
+  pub description: Option<String>,
   pub required: bool,
+  pub deprecated: bool,
+  pub style: ParameterStyle,
+  pub explode: bool,
+  pub allow_reserved: bool,
+  pub allow_empty_value: bool,
+  /// content type of the parameter, only when the parameter is defined via
+  /// `content` instead of `schema`
+  pub content_type: Option<String>,
   pub schema_id: Option<NodeLocation>,
 }
 
@@ -25,11 +35,46 @@ impl ParameterContainer {
     self.0.name.clone()
   }
 
+  #[wasm_bindgen(getter, js_name = "description")]
+  pub fn description(&self) -> Option<String> {
+    self.0.description.clone()
+  }
+
   #[wasm_bindgen(getter, js_name = "required")]
   pub fn required(&self) -> bool {
     self.0.required
   }
 
+  #[wasm_bindgen(getter, js_name = "deprecated")]
+  pub fn deprecated(&self) -> bool {
+    self.0.deprecated
+  }
+
+  #[wasm_bindgen(getter, js_name = "style")]
+  pub fn style(&self) -> String {
+    self.0.style.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "explode")]
+  pub fn explode(&self) -> bool {
+    self.0.explode
+  }
+
+  #[wasm_bindgen(getter, js_name = "allowReserved")]
+  pub fn allow_reserved(&self) -> bool {
+    self.0.allow_reserved
+  }
+
+  #[wasm_bindgen(getter, js_name = "allowEmptyValue")]
+  pub fn allow_empty_value(&self) -> bool {
+    self.0.allow_empty_value
+  }
+
+  #[wasm_bindgen(getter, js_name = "contentType")]
+  pub fn content_type(&self) -> Option<String> {
+    self.0.content_type.clone()
+  }
+
   #[wasm_bindgen(getter, js_name = "schemaId")]
   pub fn schema_id(&self) -> Option<String> {
     Some(self.0.schema_id.as_ref()?.to_string())
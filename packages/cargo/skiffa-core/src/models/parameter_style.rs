@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterStyle {
+  Matrix,
+  Label,
+  Form,
+  Simple,
+  SpaceDelimited,
+  PipeDelimited,
+  DeepObject,
+}
+
+impl ParameterStyle {
+  /// The default style of a parameter, depending on the location of the
+  /// parameter (the value of `in`).
+  ///
+  pub fn default_for(r#in: &str) -> Self {
+    match r#in {
+      "query" | "cookie" => Self::Form,
+      _ => Self::Simple,
+    }
+  }
+
+  /// The default value of explode, depending on the style
+  ///
+  pub fn default_explode(&self) -> bool {
+    *self == Self::Form
+  }
+}
+
+impl FromStr for ParameterStyle {
+  type Err = ParameterStyleParseError;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    Ok(match value {
+      "matrix" => Self::Matrix,
+      "label" => Self::Label,
+      "form" => Self::Form,
+      "simple" => Self::Simple,
+      "spaceDelimited" => Self::SpaceDelimited,
+      "pipeDelimited" => Self::PipeDelimited,
+      "deepObject" => Self::DeepObject,
+      _ => Err(ParameterStyleParseError)?,
+    })
+  }
+}
+
+#[allow(clippy::to_string_trait_impl)]
+impl ToString for ParameterStyle {
+  fn to_string(&self) -> String {
+    match self {
+      Self::Matrix => "matrix".to_owned(),
+      Self::Label => "label".to_owned(),
+      Self::Form => "form".to_owned(),
+      Self::Simple => "simple".to_owned(),
+      Self::SpaceDelimited => "spaceDelimited".to_owned(),
+      Self::PipeDelimited => "pipeDelimited".to_owned(),
+      Self::DeepObject => "deepObject".to_owned(),
+    }
+  }
+}
+
+pub struct ParameterStyleParseError;
@@ -1,12 +1,15 @@
-use super::{Operation, OperationContainer};
+use super::{Operation, OperationContainer, Server, ServerContainer};
 use crate::utils::NodeLocation;
 use std::rc;
 use wasm_bindgen::prelude::*;
 
+#[derive(Clone)]
 pub struct Path {
   pub id: usize,
   pub location: NodeLocation,
   pub pattern: String,
+  /// servers of the path, inherited from the api if the path does not define any
+  pub servers: Vec<rc::Rc<Server>>,
   pub operations: Vec<rc::Rc<Operation>>,
 }
 
@@ -31,6 +34,17 @@ impl PathContainer {
     self.0.pattern.clone()
   }
 
+  #[wasm_bindgen(getter, js_name = "servers")]
+  pub fn servers(&self) -> Vec<ServerContainer> {
+    self
+      .0
+      .servers
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+
   #[wasm_bindgen(getter, js_name = "operations")]
   pub fn operations(&self) -> Vec<OperationContainer> {
     self
@@ -0,0 +1,53 @@
+use super::{ServerVariable, ServerVariableContainer};
+use crate::utils::NodeLocation;
+use std::rc;
+use wasm_bindgen::prelude::*;
+
+pub struct Server {
+  pub location: NodeLocation,
+  /// url template of the server, relative urls are resolved against the
+  /// location of the document. The template may contain variables in curly
+  /// braces.
+  pub url: String,
+  pub description: Option<String>,
+  pub variables: Vec<rc::Rc<ServerVariable>>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ServerContainer(rc::Rc<Server>);
+
+#[wasm_bindgen]
+impl ServerContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "url")]
+  pub fn url(&self) -> String {
+    self.0.url.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "description")]
+  pub fn description(&self) -> Option<String> {
+    self.0.description.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "variables")]
+  pub fn variables(&self) -> Vec<ServerVariableContainer> {
+    self
+      .0
+      .variables
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+}
+
+impl From<rc::Rc<Server>> for ServerContainer {
+  fn from(interior: rc::Rc<Server>) -> Self {
+    Self(interior)
+  }
+}
@@ -0,0 +1,49 @@
+use crate::utils::NodeLocation;
+use std::rc;
+use wasm_bindgen::prelude::*;
+
+pub struct ServerVariable {
+  pub location: NodeLocation,
+  pub name: String,
+  pub default: String,
+  pub r#enum: Vec<String>,
+  pub description: Option<String>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct ServerVariableContainer(rc::Rc<ServerVariable>);
+
+#[wasm_bindgen]
+impl ServerVariableContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "name")]
+  pub fn name(&self) -> String {
+    self.0.name.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "default")]
+  pub fn default(&self) -> String {
+    self.0.default.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "enum")]
+  pub fn r#enum(&self) -> Vec<String> {
+    self.0.r#enum.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "description")]
+  pub fn description(&self) -> Option<String> {
+    self.0.description.clone()
+  }
+}
+
+impl From<rc::Rc<ServerVariable>> for ServerVariableContainer {
+  fn from(interior: rc::Rc<ServerVariable>) -> Self {
+    Self(interior)
+  }
+}
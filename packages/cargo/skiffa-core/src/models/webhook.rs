@@ -0,0 +1,45 @@
+use super::{Operation, OperationContainer};
+use crate::utils::NodeLocation;
+use std::rc;
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone)]
+pub struct Webhook {
+  pub location: NodeLocation,
+  pub name: String,
+  pub operations: Vec<rc::Rc<Operation>>,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct WebhookContainer(rc::Rc<Webhook>);
+
+#[wasm_bindgen]
+impl WebhookContainer {
+  #[wasm_bindgen(getter, js_name = "location")]
+  pub fn location(&self) -> String {
+    self.0.location.to_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "name")]
+  pub fn name(&self) -> String {
+    self.0.name.clone()
+  }
+
+  #[wasm_bindgen(getter, js_name = "operations")]
+  pub fn operations(&self) -> Vec<OperationContainer> {
+    self
+      .0
+      .operations
+      .iter()
+      .cloned()
+      .map(|model| model.into())
+      .collect()
+  }
+}
+
+impl From<rc::Rc<Webhook>> for WebhookContainer {
+  fn from(interior: rc::Rc<Webhook>) -> Self {
+    Self(interior)
+  }
+}
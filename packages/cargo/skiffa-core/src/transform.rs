@@ -0,0 +1,14 @@
+mod bundle;
+mod convert_swagger2;
+mod dereference;
+mod output_format;
+mod referenced_nodes;
+mod upgrade_oas30;
+
+pub use bundle::*;
+pub use convert_swagger2::*;
+pub use dereference::*;
+pub use output_format::*;
+pub use upgrade_oas30::*;
+
+use referenced_nodes::*;
@@ -0,0 +1,429 @@
+use super::load_referenced_nodes;
+use crate::documents::{DocumentContext, DocumentType};
+use crate::error::Error;
+use crate::utils::{format_reference_cycle, NodeLocation};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Bundles the document at the location, and every document it references,
+/// into one self-contained document. Every node that is referenced from
+/// another document is moved into the components of the bundled document
+/// (with a name that does not collide with existing components) and the
+/// reference is rewritten to point to the moved node. Referenced nodes that
+/// can not be a component are put in place of the reference.
+///
+/// Referenced documents that are not loaded yet (like schemas in separate
+/// files) are loaded first.
+///
+pub async fn bundle_document(
+  context: &DocumentContext,
+  retrieval_location: &NodeLocation,
+) -> Result<serde_json::Value, Error> {
+  let (bundled_node, _origins) = bundle_document_with_origins(context, retrieval_location).await?;
+  Ok(bundled_node)
+}
+
+/// Bundles the document like `bundle_document`, and returns the locations
+/// of the nodes that were moved into the bundled document (as a component or
+/// in place of a reference) by their pointer in the bundled document. So
+/// transforms of the bundled document can report on the original node.
+///
+pub(super) async fn bundle_document_with_origins(
+  context: &DocumentContext,
+  retrieval_location: &NodeLocation,
+) -> Result<(serde_json::Value, BTreeMap<Vec<String>, NodeLocation>), Error> {
+  load_referenced_nodes(context, retrieval_location).await?;
+
+  let root_location = retrieval_location.set_root();
+  let root_node = context.get_node(&root_location).ok_or(Error::NotFound)?;
+  let document_type: DocumentType = (&root_node).try_into()?;
+
+  let mut bundler = Bundler {
+    context,
+    root_location,
+    document_type,
+    component_pointers: BTreeMap::new(),
+    component_names: BTreeSet::new(),
+    components: Vec::new(),
+    inline_locations: Vec::new(),
+    origins: BTreeMap::new(),
+  };
+  bundler.collect_component_names(&root_node);
+
+  let mut bundled_node = root_node;
+  bundler.bundle_node(
+    &mut bundled_node,
+    &retrieval_location.set_pointer(Vec::new()),
+    &mut Vec::new(),
+  )?;
+
+  /*
+  bundling a component may add more components, so we process them until
+  there is nothing left
+  */
+  while let Some((location, pointer)) = bundler.components.pop() {
+    let mut node = bundler.get_node(&location)?;
+    bundler.bundle_node(&mut node, &location, &mut pointer.clone())?;
+    insert_node(&mut bundled_node, &pointer, node)?;
+    bundler.origins.insert(pointer, location);
+  }
+
+  Ok((bundled_node, bundler.origins))
+}
+
+struct Bundler<'a> {
+  context: &'a DocumentContext,
+  root_location: NodeLocation,
+  document_type: DocumentType,
+  /**
+   * pointers of the (moved) components in the bundled document, by the
+   * location of the node that was moved
+   */
+  component_pointers: BTreeMap<NodeLocation, Vec<String>>,
+  /**
+   * section and name of every component in the bundled document
+   */
+  component_names: BTreeSet<(String, String)>,
+  /**
+   * components that are not bundled yet, the location of the node that is
+   * moved and the pointer of the component in the bundled document
+   */
+  components: Vec<(NodeLocation, Vec<String>)>,
+  /**
+   * locations of the nodes that are being put in place of a reference, to
+   * detect cycles
+   */
+  inline_locations: Vec<NodeLocation>,
+  /**
+   * locations of the nodes that were moved into the bundled document, by
+   * their pointer in the bundled document
+   */
+  origins: BTreeMap<Vec<String>, NodeLocation>,
+}
+
+impl Bundler<'_> {
+  fn get_node(&self, location: &NodeLocation) -> Result<serde_json::Value, Error> {
+    self.context.get_node(location).ok_or_else(|| {
+      self.context.report_error(
+        location,
+        "could not bundle the referenced node",
+        Error::NotFound,
+      );
+      Error::NotFound
+    })
+  }
+
+  fn collect_component_names(&mut self, root_node: &serde_json::Value) {
+    for section in self.get_sections() {
+      let section_pointer = self.get_section_pointer(section);
+      let section_node = section_pointer
+        .iter()
+        .try_fold(root_node, |node, member| node.get(member));
+      for name in section_node
+        .and_then(|node| node.as_object())
+        .into_iter()
+        .flat_map(|node| node.keys())
+      {
+        self
+          .component_names
+          .insert(((*section).to_owned(), name.clone()));
+      }
+    }
+  }
+
+  fn get_sections(&self) -> &'static [&'static str] {
+    match self.document_type {
+      DocumentType::Swagger2 => &["definitions", "parameters", "responses"],
+      DocumentType::OpenApiV30 => &[
+        "schemas",
+        "responses",
+        "parameters",
+        "examples",
+        "requestBodies",
+        "headers",
+        "securitySchemes",
+        "links",
+        "callbacks",
+      ],
+      DocumentType::OpenApiV31 => &[
+        "schemas",
+        "responses",
+        "parameters",
+        "examples",
+        "requestBodies",
+        "headers",
+        "securitySchemes",
+        "links",
+        "callbacks",
+        "pathItems",
+      ],
+    }
+  }
+
+  fn get_section_pointer(&self, section: &str) -> Vec<String> {
+    match self.document_type {
+      DocumentType::Swagger2 => vec![section.to_owned()],
+      DocumentType::OpenApiV30 | DocumentType::OpenApiV31 => {
+        vec!["components".to_owned(), section.to_owned()]
+      }
+    }
+  }
+
+  /**
+  the section of the component for a node that is referenced from the
+  pointer (in the bundled document). The section is taken from the location
+  of the referenced node if that is a component, and guessed from where the
+  reference is otherwise.
+  */
+  fn get_section(
+    &self,
+    target_location: &NodeLocation,
+    pointer: &[String],
+  ) -> Option<&'static str> {
+    let sections = self.get_sections();
+    let target_pointer = target_location.get_pointer().unwrap_or_default();
+    let target_section = match target_pointer.as_slice() {
+      [components, section, _name] if components == "components" => Some(section.as_str()),
+      [section, _name] => Some(section.as_str()),
+      _ => None,
+    };
+    if let Some(section) =
+      target_section.and_then(|section| sections.iter().find(|s| **s == section))
+    {
+      return Some(section);
+    }
+
+    let schema_section = match self.document_type {
+      DocumentType::Swagger2 => "definitions",
+      DocumentType::OpenApiV30 | DocumentType::OpenApiV31 => "schemas",
+    };
+    let section = if pointer
+      .iter()
+      .any(|member| member == "schema" || member == "schemas" || member == "definitions")
+    {
+      schema_section
+    } else if pointer.last().map(String::as_str) == Some("requestBody") {
+      "requestBodies"
+    } else {
+      match pointer
+        .len()
+        .checked_sub(2)
+        .map(|index| pointer[index].as_str())
+      {
+        Some("paths") => "pathItems",
+        Some(section) => section,
+        None => return None,
+      }
+    };
+
+    sections.iter().find(|s| **s == section).copied()
+  }
+
+  /**
+  a name for a component that is moved from the location, that is not used
+  by any other component in the section
+  */
+  fn make_component_name(&mut self, section: &str, target_location: &NodeLocation) -> String {
+    let base_name = match target_location
+      .get_pointer()
+      .and_then(|pointer| pointer.last().cloned())
+    {
+      Some(name) => name,
+      None => {
+        /*
+        the whole document is referenced, use the file name without extension
+        */
+        let file_name = target_location
+          .get_path()
+          .last()
+          .cloned()
+          .unwrap_or_default();
+        match file_name.rsplit_once('.') {
+          Some((stem, _extension)) => stem.to_owned(),
+          None => file_name,
+        }
+      }
+    };
+    let base_name: String = base_name
+      .chars()
+      .map(|c| {
+        if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' {
+          c
+        } else {
+          '-'
+        }
+      })
+      .collect();
+    let base_name = if base_name.is_empty() {
+      section.to_owned()
+    } else {
+      base_name
+    };
+
+    let mut name = base_name.clone();
+    let mut suffix = 1;
+    while self
+      .component_names
+      .contains(&(section.to_owned(), name.clone()))
+    {
+      suffix += 1;
+      name = format!("{}{}", base_name, suffix);
+    }
+    self
+      .component_names
+      .insert((section.to_owned(), name.clone()));
+
+    name
+  }
+
+  /**
+  rewrite the references in the node (that came from the location and ends
+  up at the pointer in the bundled document)
+  */
+  fn bundle_node(
+    &mut self,
+    node: &mut serde_json::Value,
+    location: &NodeLocation,
+    pointer: &mut Vec<String>,
+  ) -> Result<(), Error> {
+    match node {
+      serde_json::Value::Object(object_node) => {
+        if let Some(reference) = object_node.get("$ref").and_then(|node| node.as_str()) {
+          let reference_location: NodeLocation = reference.parse()?;
+          let target_location = location.join(&reference_location);
+
+          if target_location.set_root() == self.root_location {
+            let reference =
+              make_internal_reference(target_location.get_pointer().unwrap_or_default())?;
+            object_node.insert("$ref".to_owned(), reference.into());
+            return Ok(());
+          }
+
+          if let Some(component_pointer) = self.get_component_pointer(&target_location, pointer) {
+            let reference = make_internal_reference(component_pointer)?;
+            object_node.insert("$ref".to_owned(), reference.into());
+            return Ok(());
+          }
+
+          /*
+          the referenced node can not be a component, so it is put in place of
+          the reference
+          */
+          if let Some(index) = self
+            .inline_locations
+            .iter()
+            .position(|inline_location| *inline_location == target_location)
+          {
+            self.context.report_error(
+              &target_location,
+              &format!(
+                "could not bundle reference cycle {}",
+                format_reference_cycle(&self.inline_locations[index..])
+              ),
+              Error::ReferenceCycle,
+            );
+            return Err(Error::ReferenceCycle);
+          }
+          let mut target_node = self.get_node(&target_location)?;
+          self.inline_locations.push(target_location.clone());
+          self.bundle_node(&mut target_node, &target_location, pointer)?;
+          self.inline_locations.pop();
+
+          self.origins.insert(pointer.clone(), target_location);
+          *node = target_node;
+          return Ok(());
+        }
+
+        for (member, member_node) in object_node.iter_mut() {
+          pointer.push(member.clone());
+          self.bundle_node(
+            member_node,
+            &location.push_pointer(vec![member.clone()]),
+            pointer,
+          )?;
+          pointer.pop();
+        }
+      }
+      serde_json::Value::Array(array_node) => {
+        for (index, item_node) in array_node.iter_mut().enumerate() {
+          pointer.push(index.to_string());
+          self.bundle_node(
+            item_node,
+            &location.push_pointer(vec![index.to_string()]),
+            pointer,
+          )?;
+          pointer.pop();
+        }
+      }
+      _ => {}
+    }
+
+    Ok(())
+  }
+
+  /**
+  the pointer of the component for the referenced node, the node is queued to
+  be moved if this is the first time it is referenced. None if the node can
+  not be a component.
+  */
+  fn get_component_pointer(
+    &mut self,
+    target_location: &NodeLocation,
+    pointer: &[String],
+  ) -> Option<Vec<String>> {
+    if let Some(component_pointer) = self.component_pointers.get(target_location) {
+      return Some(component_pointer.clone());
+    }
+
+    let section = self.get_section(target_location, pointer)?;
+    let name = self.make_component_name(section, target_location);
+    let component_pointer: Vec<_> = self
+      .get_section_pointer(section)
+      .into_iter()
+      .chain([name])
+      .collect();
+
+    self
+      .component_pointers
+      .insert(target_location.clone(), component_pointer.clone());
+    self
+      .components
+      .push((target_location.clone(), component_pointer.clone()));
+
+    Some(component_pointer)
+  }
+}
+
+pub(super) fn make_internal_reference(pointer: Vec<String>) -> Result<String, Error> {
+  let location: NodeLocation = "#".parse()?;
+  Ok(location.set_pointer(pointer).to_string())
+}
+
+/**
+insert the node in the root node at the pointer, objects that are missing on
+the way are created
+*/
+fn insert_node(
+  root_node: &mut serde_json::Value,
+  pointer: &[String],
+  node: serde_json::Value,
+) -> Result<(), Error> {
+  let Some((name, parent_pointer)) = pointer.split_last() else {
+    *root_node = node;
+    return Ok(());
+  };
+
+  let mut parent_node = root_node;
+  for member in parent_pointer {
+    parent_node = parent_node
+      .as_object_mut()
+      .ok_or(Error::Conflict)?
+      .entry(member.clone())
+      .or_insert_with(|| serde_json::Value::Object(Default::default()));
+  }
+  parent_node
+    .as_object_mut()
+    .ok_or(Error::Conflict)?
+    .insert(name.clone(), node);
+
+  Ok(())
+}
@@ -0,0 +1,1104 @@
+use super::bundle::make_internal_reference;
+use super::bundle_document;
+use super::upgrade_oas30::{self, upgrade_oas30_schema};
+use crate::documents::{Diagnostic, DiagnosticSeverity, DocumentContext, DocumentType};
+use crate::error::Error;
+use crate::utils::NodeLocation;
+
+/// Converts the swagger 2.0 document at the location into an OpenAPI 3.0
+/// or 3.1 document. The document is bundled first, so the converted document
+/// is self-contained and every reference in it points to the converted node.
+///
+/// Constructs that can not be converted without losing information are
+/// reported as a warning diagnostic on the node in the swagger document.
+/// Schemas of a 3.1 document are upgraded while they are converted, so the
+/// changes are reported on the node in the swagger document too.
+///
+pub async fn convert_swagger2_document(
+  context: &DocumentContext,
+  retrieval_location: &NodeLocation,
+  document_type: DocumentType,
+) -> Result<serde_json::Value, Error> {
+  let openapi_version = match document_type {
+    DocumentType::OpenApiV30 => OPENAPI_VERSION,
+    DocumentType::OpenApiV31 => upgrade_oas30::OPENAPI_VERSION,
+    DocumentType::Swagger2 => return Err(Error::DocumentTypeError),
+  };
+
+  let node = bundle_document(context, retrieval_location).await?;
+
+  let document_type: DocumentType = (&node).try_into()?;
+  if document_type != DocumentType::Swagger2 {
+    context.report_error(
+      &retrieval_location.set_root(),
+      "could not convert a document that is not a swagger 2.0 document",
+      Error::DocumentTypeError,
+    );
+    return Err(Error::DocumentTypeError);
+  }
+
+  let converter = Swagger2Converter {
+    context,
+    node: &node,
+    root_location: retrieval_location.set_pointer(Vec::new()),
+    openapi_version,
+  };
+  converter.convert_document()
+}
+
+const OPENAPI_VERSION: &str = "3.0.3";
+
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+const FORM_MEDIA_TYPES: [&str; 2] = ["application/x-www-form-urlencoded", "multipart/form-data"];
+
+const REQUEST_BODY_NAME_MEMBER: &str = "x-codegen-request-body-name";
+
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+/**
+members of a parameter, header or items object that end up in the schema
+*/
+const SCHEMA_MEMBERS: [&str; 16] = [
+  "type",
+  "format",
+  "items",
+  "default",
+  "maximum",
+  "exclusiveMaximum",
+  "minimum",
+  "exclusiveMinimum",
+  "maxLength",
+  "minLength",
+  "pattern",
+  "maxItems",
+  "minItems",
+  "uniqueItems",
+  "enum",
+  "multipleOf",
+];
+
+struct Swagger2Converter<'a> {
+  context: &'a DocumentContext,
+  /**
+   * the bundled swagger document
+   */
+  node: &'a serde_json::Value,
+  root_location: NodeLocation,
+  /**
+   * version of the converted document, schemas are upgraded for 3.1
+   */
+  openapi_version: &'static str,
+}
+
+impl Swagger2Converter<'_> {
+  fn report_note(&self, location: &NodeLocation, message: &str) {
+    self.context.report_diagnostic(Diagnostic {
+      location: location.clone(),
+      message: message.to_owned(),
+      severity: DiagnosticSeverity::Warning,
+      cause: None,
+    });
+  }
+
+  fn get_media_types(&self, node: &serde_json::Value, member: &str) -> Vec<String> {
+    let media_types = node
+      .get(member)
+      .or_else(|| self.node.get(member))
+      .and_then(|node| node.as_array())
+      .map(|node| {
+        node
+          .iter()
+          .filter_map(|node| node.as_str())
+          .map(|node| node.to_owned())
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+
+    if media_types.is_empty() {
+      vec![DEFAULT_MEDIA_TYPE.to_owned()]
+    } else {
+      media_types
+    }
+  }
+
+  fn convert_document(&self) -> Result<serde_json::Value, Error> {
+    let location = &self.root_location;
+    let mut document = serde_json::Map::new();
+    document.insert("openapi".to_owned(), self.openapi_version.into());
+
+    for (member, member_node) in self.node.as_object().into_iter().flatten() {
+      match member.as_str() {
+        "swagger"
+        | "host"
+        | "basePath"
+        | "schemes"
+        | "consumes"
+        | "produces"
+        | "paths"
+        | "definitions"
+        | "parameters"
+        | "responses"
+        | "securityDefinitions" => {}
+        _ => {
+          document.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+
+    if let Some(servers) = self.make_servers(self.node.get("schemes")) {
+      document.insert("servers".to_owned(), servers);
+    }
+
+    let mut paths = serde_json::Map::new();
+    for (path, path_node) in self
+      .node
+      .get("paths")
+      .and_then(|node| node.as_object())
+      .into_iter()
+      .flatten()
+    {
+      let path_location = location.push_pointer(vec!["paths".to_owned(), path.clone()]);
+      if path.starts_with("x-") {
+        paths.insert(path.clone(), path_node.clone());
+        continue;
+      }
+      paths.insert(
+        path.clone(),
+        self.convert_path_item(path_node, &path_location)?,
+      );
+    }
+    document.insert("paths".to_owned(), paths.into());
+
+    let components = self.convert_components()?;
+    if !components.is_empty() {
+      document.insert("components".to_owned(), components.into());
+    }
+
+    Ok(document.into())
+  }
+
+  fn convert_components(&self) -> Result<serde_json::Map<String, serde_json::Value>, Error> {
+    let location = &self.root_location;
+    let mut components = serde_json::Map::new();
+
+    let mut schemas = serde_json::Map::new();
+    for (name, node) in get_members(self.node, "definitions") {
+      let schema_location = location.push_pointer(vec!["definitions".to_owned(), name.clone()]);
+      schemas.insert(name.clone(), self.convert_schema(node, &schema_location)?);
+    }
+
+    let mut parameters = serde_json::Map::new();
+    let mut request_bodies = serde_json::Map::new();
+    for (name, node) in get_members(self.node, "parameters") {
+      let parameter_location = location.push_pointer(vec!["parameters".to_owned(), name.clone()]);
+      match node.get("in").and_then(|node| node.as_str()) {
+        Some("body") => {
+          let consumes = self.get_media_types(self.node, "consumes");
+          request_bodies.insert(
+            name.clone(),
+            self.convert_body_parameter(node, &parameter_location, &consumes)?,
+          );
+        }
+        /*
+        form data parameters are put in place of the references, they are
+        part of the request body of the operation
+        */
+        Some("formData") => {}
+        _ => {
+          parameters.insert(
+            name.clone(),
+            self.convert_parameter(node, &parameter_location)?,
+          );
+        }
+      }
+    }
+
+    let mut responses = serde_json::Map::new();
+    for (name, node) in get_members(self.node, "responses") {
+      let response_location = location.push_pointer(vec!["responses".to_owned(), name.clone()]);
+      let produces = self.get_media_types(self.node, "produces");
+      responses.insert(
+        name.clone(),
+        self.convert_response(node, &response_location, &produces)?,
+      );
+    }
+
+    let mut security_schemes = serde_json::Map::new();
+    for (name, node) in get_members(self.node, "securityDefinitions") {
+      let security_scheme_location =
+        location.push_pointer(vec!["securityDefinitions".to_owned(), name.clone()]);
+      security_schemes.insert(
+        name.clone(),
+        self.convert_security_scheme(node, &security_scheme_location),
+      );
+    }
+
+    for (section, section_node) in [
+      ("schemas", schemas),
+      ("parameters", parameters),
+      ("requestBodies", request_bodies),
+      ("responses", responses),
+      ("securitySchemes", security_schemes),
+    ] {
+      if !section_node.is_empty() {
+        components.insert(section.to_owned(), section_node.into());
+      }
+    }
+
+    Ok(components)
+  }
+
+  /**
+  servers for the host and base path of the document and the schemes, None
+  if there is nothing to make a server of
+  */
+  fn make_servers(&self, schemes: Option<&serde_json::Value>) -> Option<serde_json::Value> {
+    let host = self.node.get("host").and_then(|node| node.as_str());
+    let base_path = self.node.get("basePath").and_then(|node| node.as_str());
+    let schemes: Vec<_> = schemes
+      .and_then(|node| node.as_array())
+      .into_iter()
+      .flatten()
+      .filter_map(|node| node.as_str())
+      .collect();
+
+    if host.is_none() && base_path.is_none() && schemes.is_empty() {
+      return None;
+    }
+
+    let base_path = base_path.unwrap_or("");
+    let urls: Vec<_> = match host {
+      Some(host) if schemes.is_empty() => vec![format!("//{}{}", host, base_path)],
+      Some(host) => schemes
+        .iter()
+        .map(|scheme| format!("{}://{}{}", scheme, host, base_path))
+        .collect(),
+      None if base_path.is_empty() => vec!["/".to_owned()],
+      None => vec![base_path.to_owned()],
+    };
+
+    Some(
+      urls
+        .into_iter()
+        .map(|url| serde_json::json!({ "url": url }))
+        .collect(),
+    )
+  }
+
+  /**
+  rewrite a reference to a node in the swagger document to a reference to
+  where the converted node ends up
+  */
+  fn convert_reference(&self, reference: &str, location: &NodeLocation) -> Result<String, Error> {
+    let reference_location: NodeLocation = reference.parse()?;
+    let pointer = reference_location.get_pointer().unwrap_or_default();
+
+    let section = match pointer.first().map(|member| member.as_str()) {
+      Some("definitions") => "schemas",
+      Some("responses") => "responses",
+      Some("securityDefinitions") => "securitySchemes",
+      Some("parameters") => {
+        let parameter_in = pointer
+          .get(1)
+          .and_then(|name| self.node.get("parameters")?.get(name)?.get("in")?.as_str());
+        if parameter_in == Some("body") {
+          "requestBodies"
+        } else {
+          "parameters"
+        }
+      }
+      /*
+      path items are at the same place in both documents
+      */
+      Some("paths") if pointer.len() <= 2 => return Ok(reference.to_owned()),
+      _ => {
+        self.report_note(
+          location,
+          &format!("could not convert the reference {}", reference),
+        );
+        return Ok(reference.to_owned());
+      }
+    };
+
+    make_internal_reference(
+      ["components".to_owned(), section.to_owned()]
+        .into_iter()
+        .chain(pointer.into_iter().skip(1))
+        .collect(),
+    )
+  }
+
+  fn convert_path_item(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    let mut path_item = serde_json::Map::new();
+
+    /*
+    body and form data parameters of the path item become part of the request
+    body of every operation
+    */
+    let mut parameters = Vec::new();
+    let mut request_parameters = Vec::new();
+    for (index, parameter_node) in get_items(node, "parameters") {
+      let parameter_location = location.push_pointer(vec!["parameters".to_owned(), index]);
+      let (resolved_node, resolved_location) =
+        self.resolve_parameter(parameter_node, &parameter_location)?;
+      match resolved_node.get("in").and_then(|node| node.as_str()) {
+        Some("body") | Some("formData") => {
+          request_parameters.push((parameter_node, resolved_node, resolved_location))
+        }
+        _ => parameters.push(self.convert_parameter(parameter_node, &parameter_location)?),
+      }
+    }
+
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      match member.as_str() {
+        "parameters" => {}
+        "$ref" => {
+          let reference = member_node.as_str().unwrap_or_default();
+          path_item.insert(
+            member.clone(),
+            self.convert_reference(reference, &member_location)?.into(),
+          );
+        }
+        method if METHODS.contains(&method) => {
+          path_item.insert(
+            member.clone(),
+            self.convert_operation(member_node, &member_location, &request_parameters)?,
+          );
+        }
+        _ => {
+          path_item.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+
+    if !parameters.is_empty() {
+      path_item.insert("parameters".to_owned(), parameters.into());
+    }
+
+    Ok(path_item.into())
+  }
+
+  fn convert_operation(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+    path_request_parameters: &[(&serde_json::Value, serde_json::Value, NodeLocation)],
+  ) -> Result<serde_json::Value, Error> {
+    let mut operation = serde_json::Map::new();
+    let consumes = self.get_media_types(node, "consumes");
+    let produces = self.get_media_types(node, "produces");
+
+    let mut parameters = Vec::new();
+    let mut request_parameters = Vec::new();
+    for (index, parameter_node) in get_items(node, "parameters") {
+      let parameter_location = location.push_pointer(vec!["parameters".to_owned(), index]);
+      let (resolved_node, resolved_location) =
+        self.resolve_parameter(parameter_node, &parameter_location)?;
+      match resolved_node.get("in").and_then(|node| node.as_str()) {
+        Some("body") | Some("formData") => {
+          request_parameters.push((parameter_node, resolved_node, resolved_location))
+        }
+        _ => parameters.push(self.convert_parameter(parameter_node, &parameter_location)?),
+      }
+    }
+
+    /*
+    parameters of the path item are overridden by parameters of the operation
+    with the same name and location
+    */
+    for (parameter_node, resolved_node, resolved_location) in path_request_parameters {
+      let key = get_parameter_key(resolved_node);
+      if !request_parameters
+        .iter()
+        .any(|(_, operation_node, _)| get_parameter_key(operation_node) == key)
+      {
+        request_parameters.push((
+          parameter_node,
+          resolved_node.clone(),
+          resolved_location.clone(),
+        ));
+      }
+    }
+
+    let (body_parameters, form_parameters): (Vec<_>, Vec<_>) = request_parameters
+      .into_iter()
+      .partition(|(_, resolved_node, _)| {
+        resolved_node.get("in").and_then(|node| node.as_str()) == Some("body")
+      });
+
+    if let Some((parameter_node, resolved_node, resolved_location)) = body_parameters.first() {
+      if !form_parameters.is_empty() {
+        self.report_note(
+          location,
+          "could not convert both body and form data parameters, only the body is converted",
+        );
+      }
+
+      /*
+      the request body component has the media types of the document, so we
+      can only refer to it if the operation does not have its own
+      */
+      let request_body = match parameter_node.get("$ref").and_then(|node| node.as_str()) {
+        Some(reference) if node.get("consumes").is_none() => {
+          serde_json::json!({ "$ref": self.convert_reference(reference, location)? })
+        }
+        _ => self.convert_body_parameter(resolved_node, resolved_location, &consumes)?,
+      };
+      operation.insert("requestBody".to_owned(), request_body);
+    } else if !form_parameters.is_empty() {
+      let form_parameters: Vec<_> = form_parameters
+        .iter()
+        .map(|(_, resolved_node, resolved_location)| (resolved_node, resolved_location))
+        .collect();
+      operation.insert(
+        "requestBody".to_owned(),
+        self.convert_form_parameters(&form_parameters, location, &consumes)?,
+      );
+    }
+
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      match member.as_str() {
+        "parameters" | "consumes" | "produces" => {}
+        "schemes" => {
+          if let Some(servers) = self.make_servers(Some(member_node)) {
+            operation.insert("servers".to_owned(), servers);
+          }
+        }
+        "responses" => {
+          let mut responses = serde_json::Map::new();
+          for (status, response_node) in member_node.as_object().into_iter().flatten() {
+            let response_location = member_location.push_pointer(vec![status.clone()]);
+            if status.starts_with("x-") {
+              responses.insert(status.clone(), response_node.clone());
+              continue;
+            }
+            responses.insert(
+              status.clone(),
+              self.convert_response(response_node, &response_location, &produces)?,
+            );
+          }
+          operation.insert(member.clone(), responses.into());
+        }
+        _ => {
+          operation.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+
+    if !parameters.is_empty() {
+      operation.insert("parameters".to_owned(), parameters.into());
+    }
+
+    Ok(operation.into())
+  }
+
+  /**
+  the parameter a reference points to, or the parameter itself if it is not a
+  reference
+  */
+  fn resolve_parameter(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<(serde_json::Value, NodeLocation), Error> {
+    let Some(reference) = node.get("$ref").and_then(|node| node.as_str()) else {
+      return Ok((node.clone(), location.clone()));
+    };
+
+    let reference_location: NodeLocation = reference.parse()?;
+    let target_location = self.root_location.join(&reference_location);
+    let target_node = target_location
+      .get_pointer()
+      .unwrap_or_default()
+      .iter()
+      .try_fold(self.node, |node, member| match node {
+        serde_json::Value::Array(array_node) => array_node.get(member.parse::<usize>().ok()?),
+        _ => node.get(member),
+      })
+      .ok_or_else(|| {
+        self.context.report_error(
+          location,
+          "could not convert the referenced parameter",
+          Error::NotFound,
+        );
+        Error::NotFound
+      })?;
+
+    Ok((target_node.clone(), target_location))
+  }
+
+  fn convert_parameter(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    if let Some(reference) = node.get("$ref").and_then(|node| node.as_str()) {
+      return Ok(serde_json::json!({ "$ref": self.convert_reference(reference, location)? }));
+    }
+
+    let mut parameter = serde_json::Map::new();
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      match member.as_str() {
+        "collectionFormat" => {}
+        member if SCHEMA_MEMBERS.contains(&member) => {}
+        _ => {
+          parameter.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+
+    if node.get("type").and_then(|node| node.as_str()) == Some("file") {
+      self.report_note(
+        location,
+        "could not convert a file parameter that is not a form data parameter",
+      );
+    }
+
+    if node.get("type").and_then(|node| node.as_str()) == Some("array") {
+      let parameter_in = node.get("in").and_then(|node| node.as_str()).unwrap_or("");
+      let collection_format = node
+        .get("collectionFormat")
+        .and_then(|node| node.as_str())
+        .unwrap_or("csv");
+      let (style, explode) = match (parameter_in, collection_format) {
+        ("query", "csv") => ("form", false),
+        ("query", "ssv") => ("spaceDelimited", false),
+        ("query", "pipes") => ("pipeDelimited", false),
+        ("query", "multi") => ("form", true),
+        ("path" | "header", "csv") => ("simple", false),
+        _ => {
+          self.report_note(
+            location,
+            &format!(
+              "could not convert the collection format {} of a {} parameter",
+              collection_format, parameter_in
+            ),
+          );
+          /*
+          form is not an allowed style for path and header parameters
+          */
+          match parameter_in {
+            "path" | "header" => ("simple", false),
+            _ => ("form", false),
+          }
+        }
+      };
+      parameter.insert("style".to_owned(), style.into());
+      parameter.insert("explode".to_owned(), explode.into());
+    }
+
+    parameter.insert("schema".to_owned(), self.make_items_schema(node, location)?);
+
+    Ok(parameter.into())
+  }
+
+  fn convert_body_parameter(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+    consumes: &[String],
+  ) -> Result<serde_json::Value, Error> {
+    let mut request_body = serde_json::Map::new();
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      match member.as_str() {
+        "description" | "required" => {
+          request_body.insert(member.clone(), member_node.clone());
+        }
+        /*
+        a request body has no name, code generators read it from this
+        extension (unless the body parameter has it already)
+        */
+        "name" => {
+          request_body
+            .entry(REQUEST_BODY_NAME_MEMBER)
+            .or_insert_with(|| member_node.clone());
+        }
+        member if member.starts_with("x-") => {
+          request_body.insert(member.to_owned(), member_node.clone());
+        }
+        _ => {}
+      }
+    }
+
+    let schema = match node.get("schema") {
+      Some(schema_node) => self.convert_schema(
+        schema_node,
+        &location.push_pointer(vec!["schema".to_owned()]),
+      )?,
+      None => serde_json::json!({}),
+    };
+    let content: serde_json::Map<_, _> = consumes
+      .iter()
+      .map(|media_type| (media_type.clone(), serde_json::json!({ "schema": schema })))
+      .collect();
+    request_body.insert("content".to_owned(), content.into());
+
+    Ok(request_body.into())
+  }
+
+  /**
+  form data parameters become the properties of the schema of a form request
+  body
+  */
+  fn convert_form_parameters(
+    &self,
+    parameters: &[(&serde_json::Value, &NodeLocation)],
+    location: &NodeLocation,
+    consumes: &[String],
+  ) -> Result<serde_json::Value, Error> {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    let mut encoding = serde_json::Map::new();
+
+    for (node, parameter_location) in parameters {
+      let name = node
+        .get("name")
+        .and_then(|node| node.as_str())
+        .unwrap_or_default()
+        .to_owned();
+
+      let mut schema = self.make_items_schema(node, parameter_location)?;
+      if let (Some(description), Some(schema)) = (node.get("description"), schema.as_object_mut()) {
+        schema.insert("description".to_owned(), description.clone());
+      }
+      properties.insert(name.clone(), schema);
+
+      if node.get("required").and_then(|node| node.as_bool()) == Some(true) {
+        required.push(serde_json::Value::from(name.clone()));
+      }
+
+      if node.get("type").and_then(|node| node.as_str()) == Some("array") {
+        let collection_format = node
+          .get("collectionFormat")
+          .and_then(|node| node.as_str())
+          .unwrap_or("csv");
+        let (style, explode) = match collection_format {
+          "csv" => ("form", false),
+          "ssv" => ("spaceDelimited", false),
+          "pipes" => ("pipeDelimited", false),
+          "multi" => ("form", true),
+          _ => {
+            self.report_note(
+              parameter_location,
+              &format!(
+                "could not convert the collection format {} of a formData parameter",
+                collection_format
+              ),
+            );
+            ("form", false)
+          }
+        };
+        encoding.insert(
+          name,
+          serde_json::json!({ "style": style, "explode": explode }),
+        );
+      }
+    }
+
+    let mut schema = serde_json::Map::new();
+    schema.insert("type".to_owned(), "object".into());
+    schema.insert("properties".to_owned(), properties.into());
+    if !required.is_empty() {
+      schema.insert("required".to_owned(), required.into());
+    }
+
+    let mut media_types: Vec<_> = consumes
+      .iter()
+      .filter(|media_type| FORM_MEDIA_TYPES.contains(&media_type.as_str()))
+      .cloned()
+      .collect();
+    if media_types.is_empty() {
+      let has_file = parameters
+        .iter()
+        .any(|(node, _)| node.get("type").and_then(|node| node.as_str()) == Some("file"));
+      if consumes
+        .iter()
+        .any(|media_type| media_type != DEFAULT_MEDIA_TYPE)
+      {
+        self.report_note(
+          location,
+          "could not convert form data parameters without a form media type",
+        );
+      }
+      media_types.push(if has_file {
+        "multipart/form-data".to_owned()
+      } else {
+        "application/x-www-form-urlencoded".to_owned()
+      });
+    }
+
+    let mut media_type = serde_json::Map::new();
+    media_type.insert("schema".to_owned(), schema.into());
+    if !encoding.is_empty() {
+      media_type.insert("encoding".to_owned(), encoding.into());
+    }
+    let content: serde_json::Map<_, _> = media_types
+      .into_iter()
+      .map(|media_type_name| (media_type_name, media_type.clone().into()))
+      .collect();
+
+    Ok(serde_json::json!({ "content": content }))
+  }
+
+  fn convert_response(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+    produces: &[String],
+  ) -> Result<serde_json::Value, Error> {
+    if let Some(reference) = node.get("$ref").and_then(|node| node.as_str()) {
+      return Ok(serde_json::json!({ "$ref": self.convert_reference(reference, location)? }));
+    }
+
+    let mut response = serde_json::Map::new();
+    let mut content = serde_json::Map::new();
+
+    if let Some(schema_node) = node.get("schema") {
+      let schema = self.convert_schema(
+        schema_node,
+        &location.push_pointer(vec!["schema".to_owned()]),
+      )?;
+      for media_type in produces {
+        content.insert(media_type.clone(), serde_json::json!({ "schema": schema }));
+      }
+    }
+
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      match member.as_str() {
+        "schema" => {}
+        "examples" => {
+          for (media_type, example) in member_node.as_object().into_iter().flatten() {
+            let media_type_node = content
+              .entry(media_type.clone())
+              .or_insert_with(|| serde_json::json!({}));
+            if let Some(media_type_node) = media_type_node.as_object_mut() {
+              media_type_node.insert("example".to_owned(), example.clone());
+            }
+          }
+        }
+        "headers" => {
+          let mut headers = serde_json::Map::new();
+          for (name, header_node) in member_node.as_object().into_iter().flatten() {
+            let header_location = member_location.push_pointer(vec![name.clone()]);
+            headers.insert(
+              name.clone(),
+              self.convert_header(header_node, &header_location)?,
+            );
+          }
+          response.insert(member.clone(), headers.into());
+        }
+        _ => {
+          response.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+
+    if !content.is_empty() {
+      response.insert("content".to_owned(), content.into());
+    }
+
+    Ok(response.into())
+  }
+
+  fn convert_header(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    let mut header = serde_json::Map::new();
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      match member.as_str() {
+        "collectionFormat" => {
+          if member_node.as_str() != Some("csv") {
+            self.report_note(
+              location,
+              &format!(
+                "could not convert the collection format {} of a header",
+                member_node.as_str().unwrap_or_default()
+              ),
+            );
+          }
+        }
+        member if SCHEMA_MEMBERS.contains(&member) => {}
+        _ => {
+          header.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+    header.insert("schema".to_owned(), self.make_items_schema(node, location)?);
+
+    Ok(header.into())
+  }
+
+  /**
+  schemas of a 3.1 document are upgraded, the schema came from the location
+  */
+  fn upgrade_schema(
+    &self,
+    schema: Result<serde_json::Value, Error>,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    let mut schema = schema?;
+    if self.openapi_version == upgrade_oas30::OPENAPI_VERSION {
+      upgrade_oas30_schema(self.context, &mut schema, location);
+    }
+    Ok(schema)
+  }
+
+  /**
+  make a schema of the schema members of a parameter, header or items object
+  */
+  fn make_items_schema(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    self.upgrade_schema(self.make_items_schema_node(node, location), location)
+  }
+
+  fn make_items_schema_node(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    let mut schema = serde_json::Map::new();
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      match member.as_str() {
+        "items" => {
+          let items_location = location.push_pointer(vec![member.clone()]);
+          if member_node
+            .get("collectionFormat")
+            .and_then(|node| node.as_str())
+            .unwrap_or("csv")
+            != "csv"
+          {
+            self.report_note(
+              &items_location,
+              "could not convert the collection format of nested items",
+            );
+          }
+          schema.insert(
+            member.clone(),
+            self.make_items_schema_node(member_node, &items_location)?,
+          );
+        }
+        "type" if member_node.as_str() == Some("file") => {
+          schema.insert("type".to_owned(), "string".into());
+          schema.insert("format".to_owned(), "binary".into());
+        }
+        member if SCHEMA_MEMBERS.contains(&member) => {
+          schema.insert(member.to_owned(), member_node.clone());
+        }
+        _ => {}
+      }
+    }
+
+    Ok(schema.into())
+  }
+
+  fn convert_schema(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    self.upgrade_schema(self.convert_schema_node(node, location), location)
+  }
+
+  fn convert_schema_node(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> Result<serde_json::Value, Error> {
+    let Some(object_node) = node.as_object() else {
+      return Ok(node.clone());
+    };
+
+    let mut schema = serde_json::Map::new();
+    for (member, member_node) in object_node {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      match member.as_str() {
+        "$ref" => {
+          let reference = member_node.as_str().unwrap_or_default();
+          schema.insert(
+            member.clone(),
+            self.convert_reference(reference, &member_location)?.into(),
+          );
+        }
+        "type" if member_node.as_str() == Some("file") => {
+          schema.insert("type".to_owned(), "string".into());
+          schema.insert("format".to_owned(), "binary".into());
+        }
+        "format" if object_node.get("type").and_then(|node| node.as_str()) == Some("file") => {}
+        "x-nullable" => {
+          schema.insert("nullable".to_owned(), member_node.clone());
+        }
+        "discriminator" => {
+          let discriminator = match member_node.as_str() {
+            Some(property_name) => {
+              self.report_note(
+                &member_location,
+                "converted discriminator to an object, its values must be schema names",
+              );
+              serde_json::json!({ "propertyName": property_name })
+            }
+            None => member_node.clone(),
+          };
+          schema.insert(member.clone(), discriminator);
+        }
+        "properties" => {
+          let mut properties = serde_json::Map::new();
+          for (name, property_node) in member_node.as_object().into_iter().flatten() {
+            properties.insert(
+              name.clone(),
+              self.convert_schema_node(
+                property_node,
+                &member_location.push_pointer(vec![name.clone()]),
+              )?,
+            );
+          }
+          schema.insert(member.clone(), properties.into());
+        }
+        "items" | "additionalProperties" | "not" => {
+          schema.insert(
+            member.clone(),
+            self.convert_schema_node(member_node, &member_location)?,
+          );
+        }
+        "allOf" | "anyOf" | "oneOf" => {
+          let mut items = Vec::new();
+          for (index, item_node) in member_node.as_array().into_iter().flatten().enumerate() {
+            items.push(self.convert_schema_node(
+              item_node,
+              &member_location.push_pointer(vec![index.to_string()]),
+            )?);
+          }
+          schema.insert(member.clone(), items.into());
+        }
+        _ => {
+          schema.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+
+    Ok(schema.into())
+  }
+
+  fn convert_security_scheme(
+    &self,
+    node: &serde_json::Value,
+    location: &NodeLocation,
+  ) -> serde_json::Value {
+    let mut security_scheme = serde_json::Map::new();
+    for (member, member_node) in node.as_object().into_iter().flatten() {
+      match member.as_str() {
+        "type" | "flow" | "authorizationUrl" | "tokenUrl" | "scopes" => {}
+        _ => {
+          security_scheme.insert(member.clone(), member_node.clone());
+        }
+      }
+    }
+
+    match node.get("type").and_then(|node| node.as_str()) {
+      Some("basic") => {
+        security_scheme.insert("type".to_owned(), "http".into());
+        security_scheme.insert("scheme".to_owned(), "basic".into());
+      }
+      Some("oauth2") => {
+        security_scheme.insert("type".to_owned(), "oauth2".into());
+
+        let flow = node
+          .get("flow")
+          .and_then(|node| node.as_str())
+          .unwrap_or("");
+        let (flow_name, members): (_, &[_]) = match flow {
+          "implicit" => ("implicit", &["authorizationUrl"]),
+          "password" => ("password", &["tokenUrl"]),
+          "application" => ("clientCredentials", &["tokenUrl"]),
+          "accessCode" => ("authorizationCode", &["authorizationUrl", "tokenUrl"]),
+          _ => {
+            self.report_note(
+              location,
+              &format!("could not convert the oauth2 flow {}", flow),
+            );
+            return security_scheme.into();
+          }
+        };
+
+        let mut flow_node = serde_json::Map::new();
+        for member in members {
+          if let Some(member_node) = node.get(member) {
+            flow_node.insert((*member).to_owned(), member_node.clone());
+          }
+        }
+        flow_node.insert(
+          "scopes".to_owned(),
+          node
+            .get("scopes")
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({})),
+        );
+        security_scheme.insert(
+          "flows".to_owned(),
+          serde_json::json!({ flow_name: flow_node }),
+        );
+      }
+      Some(security_type) => {
+        security_scheme.insert("type".to_owned(), security_type.into());
+      }
+      None => {
+        self.report_note(
+          location,
+          "could not convert a security scheme without a type",
+        );
+      }
+    }
+
+    security_scheme.into()
+  }
+}
+
+fn get_members<'a>(
+  node: &'a serde_json::Value,
+  member: &str,
+) -> impl Iterator<Item = (&'a String, &'a serde_json::Value)> {
+  node
+    .get(member)
+    .and_then(|node| node.as_object())
+    .into_iter()
+    .flatten()
+    .filter(|(name, _)| !name.starts_with("x-"))
+}
+
+fn get_items<'a>(
+  node: &'a serde_json::Value,
+  member: &str,
+) -> impl Iterator<Item = (String, &'a serde_json::Value)> {
+  node
+    .get(member)
+    .and_then(|node| node.as_array())
+    .into_iter()
+    .flatten()
+    .enumerate()
+    .map(|(index, node)| (index.to_string(), node))
+}
+
+/**
+parameters are unique by name and location, header names are case insensitive
+*/
+fn get_parameter_key(node: &serde_json::Value) -> (Option<String>, Option<&str>) {
+  let name = node.get("name").and_then(|node| node.as_str());
+  let r#in = node.get("in").and_then(|node| node.as_str());
+  let name = match r#in {
+    Some("header") => name.map(str::to_lowercase),
+    _ => name.map(str::to_owned),
+  };
+  (name, r#in)
+}
@@ -0,0 +1,222 @@
+use super::bundle::make_internal_reference;
+use super::load_referenced_nodes;
+use crate::documents::{Diagnostic, DiagnosticSeverity, DocumentContext, DocumentType};
+use crate::error::Error;
+use crate::utils::NodeLocation;
+
+/// Dereferences the document at the location into one document where every
+/// reference is replaced by the node it refers to. Referenced documents that
+/// are not loaded yet (like schemas in separate files) are loaded first.
+///
+/// References that would be replaced by a node that is already being put in
+/// place (a recursive schema for instance) can not be dereferenced. These
+/// are left as a local reference to where that node ends up in the
+/// dereferenced document, so the output is still self-contained.
+///
+/// Members next to a reference override the members of the referenced node
+/// in 3.1 documents. Other documents ignore them, so they are dropped and
+/// reported.
+///
+pub async fn dereference_document(
+  context: &DocumentContext,
+  retrieval_location: &NodeLocation,
+) -> Result<serde_json::Value, Error> {
+  load_referenced_nodes(context, retrieval_location).await?;
+
+  let root_location = retrieval_location.set_pointer(Vec::new());
+  let mut node = context
+    .get_node(&root_location.set_root())
+    .ok_or(Error::NotFound)?;
+  let document_type: DocumentType = (&node).try_into()?;
+
+  let mut dereferencer = Dereferencer {
+    context,
+    document_type,
+    frames: vec![Frame {
+      location: root_location.clone(),
+      pointer: Vec::new(),
+      exit_location: None,
+    }],
+  };
+  dereferencer.dereference_node(&mut node, &root_location, &mut Vec::new())?;
+
+  Ok(node)
+}
+
+/**
+a node that is put in the dereferenced document
+*/
+struct Frame {
+  /**
+   * the location the node came from
+   */
+  location: NodeLocation,
+  /**
+   * the pointer of the node in the dereferenced document
+   */
+  pointer: Vec<String>,
+  /**
+   * location (in or under the node) of the reference that is being
+   * dereferenced, None for the innermost frame
+   */
+  exit_location: Option<NodeLocation>,
+}
+
+struct Dereferencer<'a> {
+  context: &'a DocumentContext,
+  document_type: DocumentType,
+  frames: Vec<Frame>,
+}
+
+impl Dereferencer<'_> {
+  fn report_note(&self, location: &NodeLocation, message: &str) {
+    self.context.report_diagnostic(Diagnostic {
+      location: location.clone(),
+      message: message.to_owned(),
+      severity: DiagnosticSeverity::Warning,
+      cause: None,
+    });
+  }
+
+  /**
+  replace the references in the node (that came from the location and ends
+  up at the pointer in the dereferenced document)
+  */
+  fn dereference_node(
+    &mut self,
+    node: &mut serde_json::Value,
+    location: &NodeLocation,
+    pointer: &mut Vec<String>,
+  ) -> Result<(), Error> {
+    match node {
+      serde_json::Value::Object(object_node) => {
+        if let Some(reference) = object_node.get("$ref").and_then(|node| node.as_str()) {
+          let reference_location: NodeLocation = reference.parse()?;
+          let target_location = location.join(&reference_location);
+
+          if let Some(target_pointer) = self.get_cycle_pointer(&target_location, location) {
+            let reference = make_internal_reference(target_pointer)?;
+            object_node.insert("$ref".to_owned(), reference.into());
+            return Ok(());
+          }
+
+          let mut target_node = self.context.get_node(&target_location).ok_or_else(|| {
+            self.context.report_error(
+              &target_location,
+              "could not dereference the referenced node",
+              Error::NotFound,
+            );
+            Error::NotFound
+          })?;
+
+          if let Some(frame) = self.frames.last_mut() {
+            frame.exit_location = Some(location.clone());
+          }
+          self.frames.push(Frame {
+            location: target_location.clone(),
+            pointer: pointer.clone(),
+            exit_location: None,
+          });
+          self.dereference_node(&mut target_node, &target_location, pointer)?;
+          self.frames.pop();
+          if let Some(frame) = self.frames.last_mut() {
+            frame.exit_location = None;
+          }
+
+          object_node.remove("$ref");
+          if self.document_type != DocumentType::OpenApiV31 {
+            /*
+            members next to the reference are ignored before 3.1
+            */
+            if !object_node.is_empty() {
+              let members: Vec<_> = object_node.keys().cloned().collect();
+              self.report_note(
+                location,
+                &format!(
+                  "dropped members next to the reference: {}",
+                  members.join(", ")
+                ),
+              );
+            }
+            *node = target_node;
+            return Ok(());
+          }
+
+          /*
+          members next to the reference (like a summary or description)
+          override the members of the referenced node
+          */
+          if let Some(target_object_node) = target_node.as_object_mut() {
+            for (member, mut member_node) in std::mem::take(object_node) {
+              pointer.push(member.clone());
+              self.dereference_node(
+                &mut member_node,
+                &location.push_pointer(vec![member.clone()]),
+                pointer,
+              )?;
+              pointer.pop();
+              target_object_node.insert(member, member_node);
+            }
+          }
+
+          *node = target_node;
+          return Ok(());
+        }
+
+        for (member, member_node) in object_node.iter_mut() {
+          pointer.push(member.clone());
+          self.dereference_node(
+            member_node,
+            &location.push_pointer(vec![member.clone()]),
+            pointer,
+          )?;
+          pointer.pop();
+        }
+      }
+      serde_json::Value::Array(array_node) => {
+        for (index, item_node) in array_node.iter_mut().enumerate() {
+          pointer.push(index.to_string());
+          self.dereference_node(
+            item_node,
+            &location.push_pointer(vec![index.to_string()]),
+            pointer,
+          )?;
+          pointer.pop();
+        }
+      }
+      _ => {}
+    }
+
+    Ok(())
+  }
+
+  /**
+  if the target of the reference at the location is a node that is already
+  being put in the dereferenced document, then putting it in place again
+  would never end. In that case this returns the pointer of that node in the
+  dereferenced document.
+  */
+  fn get_cycle_pointer(
+    &self,
+    target_location: &NodeLocation,
+    location: &NodeLocation,
+  ) -> Option<Vec<String>> {
+    self.frames.iter().rev().find_map(|frame| {
+      let exit_location = frame.exit_location.as_ref().unwrap_or(location);
+      if !frame.location.contains(target_location) || !target_location.contains(exit_location) {
+        return None;
+      }
+
+      let frame_pointer = frame.location.get_pointer().unwrap_or_default();
+      let target_pointer = target_location.get_pointer().unwrap_or_default();
+      Some(
+        frame
+          .pointer
+          .iter()
+          .chain(target_pointer.iter().skip(frame_pointer.len()))
+          .cloned()
+          .collect(),
+      )
+    })
+  }
+}
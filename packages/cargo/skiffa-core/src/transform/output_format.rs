@@ -0,0 +1,21 @@
+use crate::error::Error;
+use wasm_bindgen::prelude::*;
+
+/// Format of a document that is the output of a transformation
+///
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+  #[default]
+  Json,
+  Yaml,
+}
+
+impl OutputFormat {
+  pub fn serialize(&self, node: &serde_json::Value) -> Result<String, Error> {
+    match self {
+      Self::Json => serde_json::to_string_pretty(node).map_err(|_error| Error::SerializationError),
+      Self::Yaml => serde_yaml::to_string(node).map_err(|_error| Error::SerializationError),
+    }
+  }
+}
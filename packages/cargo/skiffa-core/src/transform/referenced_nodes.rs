@@ -0,0 +1,51 @@
+use crate::documents::DocumentContext;
+use crate::error::Error;
+use crate::utils::{find_node_references, NodeLocation};
+use std::collections::{BTreeSet, VecDeque};
+
+/**
+load every root node that is (transitively) referenced from the document, so
+all nodes are available in the cache when transforming. Every node is checked
+against the fetch policy like when loading documents, nodes that violate it
+are reported and not loaded.
+*/
+pub(crate) async fn load_referenced_nodes(
+  context: &DocumentContext,
+  retrieval_location: &NodeLocation,
+) -> Result<(), Error> {
+  let fetch_policy = context.get_fetch_policy();
+
+  /*
+  locations with their depth, the number of references that were followed to
+  get to the location. Locations are visited breadth first, so a location is
+  reached via the shortest chain of references and checked with that depth
+  */
+  let mut queue = VecDeque::from([(retrieval_location.set_root(), 0)]);
+  let mut visited_locations = BTreeSet::new();
+
+  let mut first_error = None;
+  while let Some((location, depth)) = queue.pop_front() {
+    if visited_locations.contains(&location) {
+      continue;
+    }
+    if let Err(error) =
+      context.check_retrieval(&fetch_policy, &location, depth, visited_locations.len() + 1)
+    {
+      first_error.get_or_insert(error);
+      continue;
+    }
+    visited_locations.insert(location.clone());
+
+    context.load_node(&location).await?;
+
+    let node = context.get_node(&location).ok_or(Error::NotFound)?;
+    for reference in find_node_references(&location, &node) {
+      queue.push_back((reference.target_location.set_root(), depth + 1));
+    }
+  }
+
+  match first_error {
+    Some(error) => Err(error),
+    None => Ok(()),
+  }
+}
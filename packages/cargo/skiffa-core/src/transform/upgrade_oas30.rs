@@ -0,0 +1,281 @@
+use super::bundle::bundle_document_with_origins;
+use crate::documents::{Diagnostic, DiagnosticSeverity, DocumentContext, DocumentType};
+use crate::error::Error;
+use crate::utils::NodeLocation;
+use std::collections::BTreeMap;
+
+/// Upgrades the OpenAPI 3.0 document at the location to an OpenAPI 3.1
+/// document. The document is bundled first, so schemas in the documents it
+/// references are upgraded too and the upgraded document is self-contained.
+///
+/// Every change to a schema is reported as an information diagnostic on the
+/// node in the original document (that may be a referenced document),
+/// changes that lose information are reported as a warning.
+///
+pub async fn upgrade_oas30_document(
+  context: &DocumentContext,
+  retrieval_location: &NodeLocation,
+) -> Result<serde_json::Value, Error> {
+  let root_location = retrieval_location.set_root();
+  context.load_node(&root_location).await?;
+  let node = context.get_node(&root_location).ok_or(Error::NotFound)?;
+
+  let document_type: DocumentType = (&node).try_into()?;
+  if document_type != DocumentType::OpenApiV30 {
+    context.report_error(
+      &root_location,
+      "could not upgrade a document that is not an OpenAPI 3.0 document",
+      Error::DocumentTypeError,
+    );
+    return Err(Error::DocumentTypeError);
+  }
+
+  let (mut node, origins) = bundle_document_with_origins(context, retrieval_location).await?;
+
+  let upgrader = Oas30Upgrader { context, origins };
+  upgrader.upgrade_node(&mut node, &[], &retrieval_location.set_pointer(Vec::new()));
+
+  if let Some(object_node) = node.as_object_mut() {
+    object_node.insert("openapi".to_owned(), OPENAPI_VERSION.into());
+  }
+
+  Ok(node)
+}
+
+/// Upgrades a schema of an OpenAPI 3.0 document, that came from the
+/// location, to an OpenAPI 3.1 schema. For documents that become a 3.1
+/// document in another way, like converted swagger documents.
+///
+pub(super) fn upgrade_oas30_schema(
+  context: &DocumentContext,
+  node: &mut serde_json::Value,
+  location: &NodeLocation,
+) {
+  let upgrader = Oas30Upgrader {
+    context,
+    origins: BTreeMap::new(),
+  };
+  upgrader.upgrade_schema(node, location);
+}
+
+pub(super) const OPENAPI_VERSION: &str = "3.1.0";
+
+struct Oas30Upgrader<'a> {
+  context: &'a DocumentContext,
+  /**
+   * locations of the nodes that were moved into the bundled document, by
+   * their pointer in the bundled document
+   */
+  origins: BTreeMap<Vec<String>, NodeLocation>,
+}
+
+impl Oas30Upgrader<'_> {
+  fn report_note(&self, location: &NodeLocation, message: &str, severity: DiagnosticSeverity) {
+    self.context.report_diagnostic(Diagnostic {
+      location: location.clone(),
+      message: message.to_owned(),
+      severity,
+      cause: None,
+    });
+  }
+
+  /**
+  the location of the node at the pointer in the bundled document, that is
+  a descendant of the node at the parent location
+  */
+  fn get_location(&self, pointer: &[String], parent_location: &NodeLocation) -> NodeLocation {
+    match self.origins.get(pointer) {
+      Some(location) => location.clone(),
+      None => parent_location.push_pointer(vec![pointer.last().cloned().unwrap_or_default()]),
+    }
+  }
+
+  /**
+  find the schemas in a node that is not a schema
+  */
+  fn upgrade_node(
+    &self,
+    node: &mut serde_json::Value,
+    pointer: &[String],
+    location: &NodeLocation,
+  ) {
+    match node {
+      serde_json::Value::Object(object_node) => {
+        let is_components = pointer == ["components"];
+        for (member, member_node) in object_node.iter_mut() {
+          let member_pointer = [pointer, std::slice::from_ref(member)].concat();
+          let member_location = self.get_location(&member_pointer, location);
+          match member.as_str() {
+            /*
+            examples and extensions are not part of the document structure
+            */
+            "example" | "examples" => {}
+            member if member.starts_with("x-") => {}
+            "schema" => self.upgrade_schema(member_node, &member_location),
+            "schemas" if is_components => {
+              for (name, schema_node) in member_node.as_object_mut().into_iter().flatten() {
+                let schema_pointer =
+                  [member_pointer.as_slice(), std::slice::from_ref(name)].concat();
+                self.upgrade_schema(
+                  schema_node,
+                  &self.get_location(&schema_pointer, &member_location),
+                );
+              }
+            }
+            _ => self.upgrade_node(member_node, &member_pointer, &member_location),
+          }
+        }
+      }
+      serde_json::Value::Array(array_node) => {
+        for (index, item_node) in array_node.iter_mut().enumerate() {
+          let item_pointer = [pointer, &[index.to_string()]].concat();
+          let item_location = self.get_location(&item_pointer, location);
+          self.upgrade_node(item_node, &item_pointer, &item_location);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  fn upgrade_schema(&self, node: &mut serde_json::Value, location: &NodeLocation) {
+    let Some(object_node) = node.as_object_mut() else {
+      return;
+    };
+
+    /*
+    a nullable schema has null as one of its types, and as one of its values
+    if it is an enum
+    */
+    if let Some(nullable) = object_node.remove("nullable") {
+      if nullable.as_bool() == Some(true) {
+        match object_node.get("type").cloned() {
+          Some(serde_json::Value::String(schema_type)) => {
+            object_node.insert("type".to_owned(), serde_json::json!([schema_type, "null"]));
+            if let Some(enum_node) = object_node
+              .get_mut("enum")
+              .and_then(|node| node.as_array_mut())
+            {
+              if !enum_node.contains(&serde_json::Value::Null) {
+                enum_node.push(serde_json::Value::Null);
+              }
+            }
+            self.report_note(
+              location,
+              "converted nullable to a type array",
+              DiagnosticSeverity::Information,
+            );
+          }
+          _ => {
+            self.report_note(
+              location,
+              "could not convert nullable without a type",
+              DiagnosticSeverity::Warning,
+            );
+          }
+        }
+      }
+    }
+
+    /*
+    exclusive minimum and maximum are the number itself instead of a flag for
+    the minimum or maximum
+    */
+    for (exclusive_member, member) in [
+      ("exclusiveMinimum", "minimum"),
+      ("exclusiveMaximum", "maximum"),
+    ] {
+      let Some(exclusive) = object_node
+        .get(exclusive_member)
+        .and_then(|node| node.as_bool())
+      else {
+        continue;
+      };
+      object_node.remove(exclusive_member);
+      if !exclusive {
+        continue;
+      }
+
+      match object_node.remove(member) {
+        Some(value) => {
+          object_node.insert(exclusive_member.to_owned(), value);
+          self.report_note(
+            location,
+            &format!("converted {} to a number", exclusive_member),
+            DiagnosticSeverity::Information,
+          );
+        }
+        None => {
+          self.report_note(
+            location,
+            &format!("could not convert {} without {}", exclusive_member, member),
+            DiagnosticSeverity::Warning,
+          );
+        }
+      }
+    }
+
+    if let Some(example) = object_node.remove("example") {
+      object_node.insert("examples".to_owned(), serde_json::json!([example]));
+      self.report_note(
+        location,
+        "converted example to examples",
+        DiagnosticSeverity::Information,
+      );
+    }
+
+    /*
+    binary strings (like file uploads) have a media type, base64 strings have
+    an encoding
+    */
+    match object_node.get("format").and_then(|node| node.as_str()) {
+      Some("binary") => {
+        object_node.remove("format");
+        object_node.insert(
+          "contentMediaType".to_owned(),
+          "application/octet-stream".into(),
+        );
+        self.report_note(
+          location,
+          "converted binary format to a content media type",
+          DiagnosticSeverity::Information,
+        );
+      }
+      Some("byte") => {
+        object_node.remove("format");
+        object_node.insert("contentEncoding".to_owned(), "base64".into());
+        self.report_note(
+          location,
+          "converted byte format to a content encoding",
+          DiagnosticSeverity::Information,
+        );
+      }
+      _ => {}
+    }
+
+    for (member, member_node) in object_node.iter_mut() {
+      let member_location = location.push_pointer(vec![member.clone()]);
+      match member.as_str() {
+        "properties" => {
+          for (name, property_node) in member_node.as_object_mut().into_iter().flatten() {
+            self.upgrade_schema(
+              property_node,
+              &member_location.push_pointer(vec![name.clone()]),
+            );
+          }
+        }
+        "items" | "additionalProperties" | "not" => {
+          self.upgrade_schema(member_node, &member_location);
+        }
+        "allOf" | "anyOf" | "oneOf" => {
+          for (index, item_node) in member_node.as_array_mut().into_iter().flatten().enumerate() {
+            self.upgrade_schema(
+              item_node,
+              &member_location.push_pointer(vec![index.to_string()]),
+            );
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+}
@@ -1,9 +1,21 @@
 mod banner;
 mod fetch_text;
+mod fetcher;
+mod http_cache;
+mod json_path;
 mod node_cache;
 mod node_location;
+mod node_position;
+mod node_reference;
+mod server_url;
 
 pub use banner::*;
 pub use fetch_text::*;
+pub use fetcher::*;
+pub use http_cache::*;
+pub use json_path::*;
 pub use node_cache::*;
 pub use node_location::*;
+pub use node_position::*;
+pub use node_reference::*;
+pub use server_url::*;
@@ -1,15 +1,136 @@
 const fs = require("fs/promises");
 
-async function fetchText(location) {
+/*
+documents that are larger than the maximum size are not read any further,
+the error code is recognized by the rust side
+*/
+function makeTooLargeError(location, maximumSize) {
+  const error = new Error(`${location} is larger than ${maximumSize} bytes`);
+  error.code = "ERR_TOO_LARGE";
+  return error;
+}
+
+/*
+requests that are not successful are errors, the error code is recognized by
+the rust side
+*/
+function makeHttpError(location, status) {
+  const error = new Error(`${location} responded with status ${status}`);
+  error.code = status === 404 ? "ERR_NOT_FOUND" : "ERR_HTTP_STATUS";
+  return error;
+}
+
+/*
+read the text of the response, without reading more than the maximum size
+*/
+async function readResponseText(location, response, maximumSize) {
+  if (maximumSize == null) {
+    const text = await response.text();
+    return text;
+  }
+
+  const contentLength = response.headers.get("content-length");
+  if (contentLength != null && Number(contentLength) > maximumSize) {
+    await response.body?.cancel();
+    throw makeTooLargeError(location, maximumSize);
+  }
+
+  const chunks = [];
+  let size = 0;
+  if (response.body != null) {
+    const reader = response.body.getReader();
+    for (;;) {
+      const { done, value } = await reader.read();
+      if (done) {
+        break;
+      }
+      size += value.byteLength;
+      if (size > maximumSize) {
+        await reader.cancel();
+        throw makeTooLargeError(location, maximumSize);
+      }
+      chunks.push(value);
+    }
+  }
+  const text = Buffer.concat(chunks).toString("utf-8");
+  return text;
+}
+
+async function fetchText(location, maximumSize) {
   const locationLower = location.toLowerCase();
   if (locationLower.startsWith("http://") || locationLower.startsWith("https://")) {
-    const result = await fetch(location);
-    const text = await result.text();
+    const response = await fetch(location);
+    if (!response.ok) {
+      await response.body?.cancel();
+      throw makeHttpError(location, response.status);
+    }
+    const text = await readResponseText(location, response, maximumSize);
     return text;
   }
 
-  const text = await fs.readFile(location, "utf-8");
-  return text;
+  if (maximumSize == null) {
+    const text = await fs.readFile(location, "utf-8");
+    return text;
+  }
+
+  const file = await fs.open(location, "r");
+  try {
+    const stat = await file.stat();
+    if (stat.size > maximumSize) {
+      throw makeTooLargeError(location, maximumSize);
+    }
+
+    /*
+    the file may grow while it is read, so at most one byte more than the
+    maximum size is read
+    */
+    const buffer = Buffer.alloc(maximumSize + 1);
+    let size = 0;
+    for (;;) {
+      const { bytesRead } = await file.read(buffer, size, buffer.length - size, null);
+      if (bytesRead === 0) {
+        break;
+      }
+      size += bytesRead;
+      if (size > maximumSize) {
+        throw makeTooLargeError(location, maximumSize);
+      }
+    }
+    const text = buffer.subarray(0, size).toString("utf-8");
+    return text;
+  } finally {
+    await file.close();
+  }
+}
+
+/*
+a conditional request for the http cache, the text is only read if the
+request was successful
+*/
+async function fetchConditional(location, etag, lastModified, maximumSize) {
+  const headers = {};
+  if (etag != null) {
+    headers["If-None-Match"] = etag;
+  }
+  if (lastModified != null) {
+    headers["If-Modified-Since"] = lastModified;
+  }
+
+  const response = await fetch(location, { headers });
+  let text = null;
+  if (response.ok) {
+    text = await readResponseText(location, response, maximumSize);
+  } else {
+    await response.body?.cancel();
+  }
+
+  return {
+    status: response.status,
+    etag: response.headers.get("etag"),
+    lastModified: response.headers.get("last-modified"),
+    text,
+  };
 }
 
 module.exports.fetchText = fetchText;
+module.exports.fetchConditional = fetchConditional;
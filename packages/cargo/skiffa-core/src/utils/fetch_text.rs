@@ -1,8 +1,51 @@
 use wasm_bindgen::prelude::*;
 
+#[derive(Debug)]
 pub enum FetchTextError {
   IoError,
   HttpError,
+  NotFound,
+  /**
+   * the document is larger than the maximum size, it is not read any further
+   */
+  TooLarge,
+}
+
+/// Returns an error if the size (in bytes) exceeds the maximum size, if any.
+///
+pub fn check_text_size(size: usize, maximum_size: Option<usize>) -> Result<(), FetchTextError> {
+  match maximum_size {
+    Some(maximum_size) if size > maximum_size => Err(FetchTextError::TooLarge),
+    _ => Ok(()),
+  }
+}
+
+/// Returns an error if the http status is not successful, a missing document
+/// (404) is `FetchTextError::NotFound`.
+///
+pub fn check_http_status(status: u16) -> Result<(), FetchTextError> {
+  match status {
+    200..=299 => Ok(()),
+    404 => Err(FetchTextError::NotFound),
+    _ => Err(FetchTextError::HttpError),
+  }
+}
+
+/**
+documents are read up to one byte more than the maximum size, so a document
+that is too large is detected without reading all of it
+*/
+#[cfg(not(target_os = "unknown"))]
+fn get_read_limit(maximum_size: Option<usize>) -> u64 {
+  maximum_size
+    .map(|maximum_size| maximum_size as u64 + 1)
+    .unwrap_or(u64::MAX)
+}
+
+#[cfg(not(target_os = "unknown"))]
+fn make_text(data: Vec<u8>, maximum_size: Option<usize>) -> Result<String, FetchTextError> {
+  check_text_size(data.len(), maximum_size)?;
+  String::from_utf8(data).map_err(|_error| FetchTextError::IoError)
 }
 
 impl From<std::io::Error> for FetchTextError {
@@ -12,8 +55,19 @@ impl From<std::io::Error> for FetchTextError {
 }
 
 impl From<JsValue> for FetchTextError {
-  fn from(_value: JsValue) -> Self {
-    Self::HttpError
+  fn from(value: JsValue) -> Self {
+    /*
+    fetch_text.js throws an error with one of these codes if the document is
+    too large or the request was not successful
+    */
+    let code = js_sys::Reflect::get(&value, &"code".into())
+      .ok()
+      .and_then(|code| code.as_string());
+    match code.as_deref() {
+      Some("ERR_TOO_LARGE") => Self::TooLarge,
+      Some("ERR_NOT_FOUND") => Self::NotFound,
+      _ => Self::HttpError,
+    }
   }
 }
 
@@ -27,31 +81,206 @@ impl From<surf::Error> for FetchTextError {
 #[wasm_bindgen(module = "/src/utils/fetch_text.js")]
 extern "C" {
   #[wasm_bindgen(catch, js_name = "fetchText")]
-  async fn fetch_text_js(location: &str) -> Result<JsValue, JsValue>;
+  async fn fetch_text_js(location: &str, maximum_size: Option<f64>) -> Result<JsValue, JsValue>;
 }
 
+/// Retrieves the text of the document at the location. If there is a
+/// maximum size (in bytes), larger documents are not read any further and
+/// `FetchTextError::TooLarge` is returned.
+///
 #[cfg(target_os = "unknown")]
-pub async fn fetch_text(location: &str) -> Result<String, FetchTextError> {
-  let text = fetch_text_js(location).await?;
+pub async fn fetch_text(
+  location: &str,
+  maximum_size: Option<usize>,
+) -> Result<String, FetchTextError> {
+  let text = fetch_text_js(
+    location,
+    maximum_size.map(|maximum_size| maximum_size as f64),
+  )
+  .await?;
   let text = text.as_string().unwrap_or_default();
+  check_text_size(text.len(), maximum_size)?;
 
   Ok(text)
 }
 
+/// Retrieves the text of the document at the location. If there is a
+/// maximum size (in bytes), larger documents are not read any further and
+/// `FetchTextError::TooLarge` is returned.
+///
 #[cfg(not(target_os = "unknown"))]
-pub async fn fetch_text(location: &str) -> Result<String, FetchTextError> {
-  use tokio::fs::File;
-  use tokio::io::AsyncReadExt;
-
+pub async fn fetch_text(
+  location: &str,
+  maximum_size: Option<usize>,
+) -> Result<String, FetchTextError> {
   if location.starts_with("http://") || location.starts_with("https://") {
-    let mut response = surf::get(location).await?;
-    let data = response.body_string().await?;
-    Ok(data)
+    fetch_http_text(location, maximum_size).await
   } else {
-    let mut file = File::open(location).await?;
-    let metadata = file.metadata().await?;
-    let mut data = String::with_capacity(metadata.len() as usize);
-    file.read_to_string(&mut data).await?;
-    Ok(data)
+    fetch_file_text(location, maximum_size).await
+  }
+}
+
+#[cfg(target_os = "unknown")]
+pub async fn fetch_http_text(
+  location: &str,
+  maximum_size: Option<usize>,
+) -> Result<String, FetchTextError> {
+  fetch_text(location, maximum_size).await
+}
+
+#[cfg(not(target_os = "unknown"))]
+pub async fn fetch_http_text(
+  location: &str,
+  maximum_size: Option<usize>,
+) -> Result<String, FetchTextError> {
+  /*
+  surf panics on a url it cannot parse, so we parse it first
+  */
+  let url: surf::Url = location
+    .parse()
+    .map_err(|_error| FetchTextError::HttpError)?;
+  let response = surf::get(url).await?;
+  read_http_text(response, maximum_size).await
+}
+
+/// Reads the body of the response, the status and the content length are
+/// checked before anything is read.
+///
+#[cfg(not(target_os = "unknown"))]
+pub async fn read_http_text(
+  response: surf::Response,
+  maximum_size: Option<usize>,
+) -> Result<String, FetchTextError> {
+  use futures::AsyncReadExt;
+
+  check_http_status(response.status().into())?;
+  if let Some(length) = response.len() {
+    check_text_size(length, maximum_size)?;
+  }
+
+  let mut data = Vec::new();
+  response
+    .take(get_read_limit(maximum_size))
+    .read_to_end(&mut data)
+    .await?;
+  make_text(data, maximum_size)
+}
+
+/// File locations may have the file scheme (file:///path), which is allowed
+/// by the fetch policy, the path follows the scheme.
+///
+fn get_file_path(location: &str) -> &str {
+  match location.get(.."file://".len()) {
+    Some(scheme) if scheme.eq_ignore_ascii_case("file://") => &location["file://".len()..],
+    _ => location,
+  }
+}
+
+#[cfg(target_os = "unknown")]
+pub async fn fetch_file_text(
+  location: &str,
+  maximum_size: Option<usize>,
+) -> Result<String, FetchTextError> {
+  fetch_text(get_file_path(location), maximum_size).await
+}
+
+#[cfg(not(target_os = "unknown"))]
+pub async fn fetch_file_text(
+  location: &str,
+  maximum_size: Option<usize>,
+) -> Result<String, FetchTextError> {
+  use tokio::io::AsyncReadExt;
+
+  let file = tokio::fs::File::open(get_file_path(location)).await?;
+  let metadata = file.metadata().await?;
+  check_text_size(metadata.len() as usize, maximum_size)?;
+
+  /*
+  the file may grow while it is read
+  */
+  let mut data = Vec::with_capacity(metadata.len() as usize);
+  file
+    .take(get_read_limit(maximum_size))
+    .read_to_end(&mut data)
+    .await?;
+  make_text(data, maximum_size)
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_fetch_file_text() {
+    let location = "../../../fixtures/specifications/echo.yaml";
+    let size = std::fs::metadata(location).unwrap().len() as usize;
+
+    assert_eq!(
+      fetch_file_text(location, Some(size)).await.unwrap().len(),
+      size
+    );
+    assert!(matches!(
+      fetch_file_text(location, Some(size - 1)).await,
+      Err(FetchTextError::TooLarge)
+    ));
+    assert_eq!(fetch_file_text(location, None).await.unwrap().len(), size);
+  }
+
+  #[tokio::test]
+  async fn test_fetch_http_text_status() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /*
+    a local stand-in for a http server, that responds with the status in the
+    path of the request
+    */
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+      for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+          continue;
+        };
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        let _ = reader.read_line(&mut request_line);
+        loop {
+          let mut line = String::new();
+          if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            break;
+          }
+        }
+
+        let status = request_line
+          .split(['/', ' '])
+          .find_map(|part| part.parse::<u16>().ok())
+          .unwrap_or(200);
+        let body = "openapi: 3.0.0";
+        let response = format!(
+          "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+          status,
+          body.len(),
+          body
+        );
+        let _ = stream.write_all(response.as_bytes());
+      }
+    });
+
+    let location = |status: u16| format!("http://{}/{}", address, status);
+
+    assert_eq!(
+      fetch_http_text(&location(200), None).await.unwrap(),
+      "openapi: 3.0.0"
+    );
+    assert!(matches!(
+      fetch_http_text(&location(404), None).await,
+      Err(FetchTextError::NotFound)
+    ));
+    assert!(matches!(
+      fetch_http_text(&location(500), None).await,
+      Err(FetchTextError::HttpError)
+    ));
   }
 }
@@ -0,0 +1,134 @@
+use super::{check_text_size, fetch_file_text, fetch_http_text, fetch_text, FetchTextError};
+use futures::future::{FutureExt, LocalBoxFuture};
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+/// Retrieves the text of a document. The location is the fetch string of a
+/// node location, the part before the hash. If there is a maximum size (in
+/// bytes) a larger document is not read any further, the fetcher returns
+/// `FetchTextError::TooLarge` instead.
+///
+pub trait Fetcher {
+  fn fetch_text<'a>(
+    &'a self,
+    location: &'a str,
+    maximum_size: Option<usize>,
+  ) -> LocalBoxFuture<'a, Result<String, FetchTextError>>;
+}
+
+/// Retrieves documents via http(s) if the location starts with a http(s)
+/// scheme, and from the file system otherwise.
+///
+#[derive(Default)]
+pub struct DefaultFetcher;
+
+impl Fetcher for DefaultFetcher {
+  fn fetch_text<'a>(
+    &'a self,
+    location: &'a str,
+    maximum_size: Option<usize>,
+  ) -> LocalBoxFuture<'a, Result<String, FetchTextError>> {
+    fetch_text(location, maximum_size).boxed_local()
+  }
+}
+
+/// Retrieves documents from the file system.
+///
+#[derive(Default)]
+pub struct FileFetcher;
+
+impl Fetcher for FileFetcher {
+  fn fetch_text<'a>(
+    &'a self,
+    location: &'a str,
+    maximum_size: Option<usize>,
+  ) -> LocalBoxFuture<'a, Result<String, FetchTextError>> {
+    fetch_file_text(location, maximum_size).boxed_local()
+  }
+}
+
+/// Retrieves documents via http(s).
+///
+#[derive(Default)]
+pub struct HttpFetcher;
+
+impl Fetcher for HttpFetcher {
+  fn fetch_text<'a>(
+    &'a self,
+    location: &'a str,
+    maximum_size: Option<usize>,
+  ) -> LocalBoxFuture<'a, Result<String, FetchTextError>> {
+    fetch_http_text(location, maximum_size).boxed_local()
+  }
+}
+
+/// Retrieves documents from memory, documents are added by their location.
+///
+#[derive(Default)]
+pub struct MemoryFetcher {
+  documents: BTreeMap<String, String>,
+}
+
+impl MemoryFetcher {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn insert(&mut self, location: impl Into<String>, data: impl Into<String>) {
+    self.documents.insert(location.into(), data.into());
+  }
+}
+
+impl Fetcher for MemoryFetcher {
+  fn fetch_text<'a>(
+    &'a self,
+    location: &'a str,
+    maximum_size: Option<usize>,
+  ) -> LocalBoxFuture<'a, Result<String, FetchTextError>> {
+    let result = self
+      .documents
+      .get(location)
+      .cloned()
+      .ok_or(FetchTextError::NotFound)
+      .and_then(|text| {
+        check_text_size(text.len(), maximum_size)?;
+        Ok(text)
+      });
+    async move { result }.boxed_local()
+  }
+}
+
+/// Retrieves documents by calling a javascript function with the location
+/// and the maximum size (or undefined). The function should return the text
+/// of the document, or a promise that resolves to the text. If the function
+/// returns anything else (like undefined) the document is not found. The
+/// function should stop reading a document that is larger than the maximum
+/// size, the text is checked against the maximum size too.
+///
+pub struct JsFetcher(js_sys::Function);
+
+impl JsFetcher {
+  pub fn new(callback: js_sys::Function) -> Self {
+    Self(callback)
+  }
+}
+
+impl Fetcher for JsFetcher {
+  fn fetch_text<'a>(
+    &'a self,
+    location: &'a str,
+    maximum_size: Option<usize>,
+  ) -> LocalBoxFuture<'a, Result<String, FetchTextError>> {
+    async move {
+      let result = self
+        .0
+        .call2(&JsValue::NULL, &location.into(), &maximum_size.into())?;
+      let promise = js_sys::Promise::resolve(&result);
+      let text = wasm_bindgen_futures::JsFuture::from(promise).await?;
+      let text = text.as_string().ok_or(FetchTextError::NotFound)?;
+      check_text_size(text.len(), maximum_size)?;
+      Ok(text)
+    }
+    .boxed_local()
+  }
+}
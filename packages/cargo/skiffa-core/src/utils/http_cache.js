@@ -0,0 +1,21 @@
+const fs = require("fs/promises");
+
+/*
+the cached entry, or undefined if there is none
+*/
+async function readCacheFile(path) {
+  try {
+    const data = await fs.readFile(path, "utf-8");
+    return data;
+  } catch {
+    return undefined;
+  }
+}
+
+async function writeCacheFile(directory, path, data) {
+  await fs.mkdir(directory, { recursive: true });
+  await fs.writeFile(path, data, "utf-8");
+}
+
+module.exports.readCacheFile = readCacheFile;
+module.exports.writeCacheFile = writeCacheFile;
@@ -0,0 +1,383 @@
+#[cfg(not(target_os = "unknown"))]
+use super::read_http_text;
+use super::{check_text_size, fetch_file_text, FetchTextError, Fetcher};
+use futures::future::{FutureExt, LocalBoxFuture};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+#[cfg(target_os = "unknown")]
+use wasm_bindgen::prelude::*;
+
+/// Retrieves documents via http(s) and keeps a copy of every response on
+/// disk, together with the validators (ETag and Last-Modified) of the
+/// response. Cached documents are revalidated with a conditional request. If
+/// the host cannot be reached the cached copy is used.
+///
+/// In offline mode no requests are made at all, documents are only served
+/// from the cache. Locations that are not http(s) are read from the file
+/// system. In wasm the requests and the cache are handled by javascript
+/// (fetch and the node file system).
+///
+pub struct HttpCacheFetcher {
+  directory: PathBuf,
+  offline: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HttpCacheEntry {
+  location: String,
+  etag: Option<String>,
+  last_modified: Option<String>,
+  body: String,
+}
+
+impl HttpCacheFetcher {
+  pub fn new(directory: impl Into<PathBuf>) -> Self {
+    Self {
+      directory: directory.into(),
+      offline: false,
+    }
+  }
+
+  pub fn set_offline(&mut self, offline: bool) {
+    self.offline = offline;
+  }
+
+  fn get_entry_path(&self, location: &str) -> PathBuf {
+    let hash = Sha256::digest(location.as_bytes());
+    let name: String = hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+    self.directory.join(format!("{}.json", name))
+  }
+
+  async fn read_entry(&self, location: &str) -> Option<HttpCacheEntry> {
+    let path = self.get_entry_path(location);
+    let data = read_cache_file(path.to_str()?).await?;
+    let entry: HttpCacheEntry = serde_json::from_str(&data).ok()?;
+    /*
+    different locations could (in theory) have the same hash
+    */
+    (entry.location == location).then_some(entry)
+  }
+
+  async fn write_entry(&self, entry: &HttpCacheEntry) -> Result<(), FetchTextError> {
+    let data = serde_json::to_string(entry).map_err(|_error| FetchTextError::IoError)?;
+    let directory = self.directory.to_str().ok_or(FetchTextError::IoError)?;
+    let path = self.get_entry_path(&entry.location);
+    let path = path.to_str().ok_or(FetchTextError::IoError)?;
+    write_cache_file(directory, path, &data).await
+  }
+
+  async fn fetch_http_text(
+    &self,
+    location: &str,
+    maximum_size: Option<usize>,
+  ) -> Result<String, FetchTextError> {
+    let entry = self.read_entry(location).await;
+    let get_cached_body = |entry: HttpCacheEntry| {
+      check_text_size(entry.body.len(), maximum_size)?;
+      Ok(entry.body)
+    };
+
+    if self.offline {
+      return entry
+        .ok_or(FetchTextError::NotFound)
+        .and_then(get_cached_body);
+    }
+
+    let response = fetch_conditional(
+      location,
+      entry.as_ref().and_then(|entry| entry.etag.as_deref()),
+      entry
+        .as_ref()
+        .and_then(|entry| entry.last_modified.as_deref()),
+      maximum_size,
+    )
+    .await;
+    let response = match response {
+      Ok(response) => response,
+      /*
+      the host is not reachable, use the cached copy if we have one
+      */
+      Err(FetchTextError::HttpError) => {
+        return entry
+          .ok_or(FetchTextError::HttpError)
+          .and_then(get_cached_body);
+      }
+      Err(error) => return Err(error),
+    };
+
+    let body = match (response.status, response.body, entry) {
+      (_, Some(body), _) => body,
+      /*
+      the cached copy is still valid, or the host has a problem
+      */
+      (304 | 500..=599, None, Some(entry)) => return get_cached_body(entry),
+      _ => return Err(FetchTextError::HttpError),
+    };
+
+    /*
+    caching is best effort, if the entry cannot be written the document is
+    still returned
+    */
+    let entry = HttpCacheEntry {
+      location: location.to_owned(),
+      etag: response.etag,
+      last_modified: response.last_modified,
+      body,
+    };
+    let _ = self.write_entry(&entry).await;
+
+    Ok(entry.body)
+  }
+}
+
+/**
+a response to a conditional request, the body is only read if the request was
+successful
+*/
+struct HttpCacheResponse {
+  status: u16,
+  etag: Option<String>,
+  last_modified: Option<String>,
+  body: Option<String>,
+}
+
+#[cfg(not(target_os = "unknown"))]
+async fn fetch_conditional(
+  location: &str,
+  etag: Option<&str>,
+  last_modified: Option<&str>,
+  maximum_size: Option<usize>,
+) -> Result<HttpCacheResponse, FetchTextError> {
+  let url: surf::Url = location
+    .parse()
+    .map_err(|_error| FetchTextError::HttpError)?;
+  let mut request = surf::get(url);
+  if let Some(etag) = etag {
+    request = request.header("If-None-Match", etag);
+  }
+  if let Some(last_modified) = last_modified {
+    request = request.header("If-Modified-Since", last_modified);
+  }
+
+  let response = request.await?;
+  let status = response.status();
+  let get_header = |name: &str| {
+    response
+      .header(name)
+      .map(|values| values.last().as_str().to_owned())
+  };
+  let etag = get_header("ETag");
+  let last_modified = get_header("Last-Modified");
+  let body = if status.is_success() {
+    Some(read_http_text(response, maximum_size).await?)
+  } else {
+    None
+  };
+
+  Ok(HttpCacheResponse {
+    status: status.into(),
+    etag,
+    last_modified,
+    body,
+  })
+}
+
+#[cfg(not(target_os = "unknown"))]
+async fn read_cache_file(path: &str) -> Option<String> {
+  tokio::fs::read_to_string(path).await.ok()
+}
+
+#[cfg(not(target_os = "unknown"))]
+async fn write_cache_file(directory: &str, path: &str, data: &str) -> Result<(), FetchTextError> {
+  tokio::fs::create_dir_all(directory).await?;
+  tokio::fs::write(path, data).await?;
+  Ok(())
+}
+
+#[cfg(target_os = "unknown")]
+#[wasm_bindgen(module = "/src/utils/fetch_text.js")]
+extern "C" {
+  #[wasm_bindgen(catch, js_name = "fetchConditional")]
+  async fn fetch_conditional_js(
+    location: &str,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    maximum_size: Option<f64>,
+  ) -> Result<JsValue, JsValue>;
+}
+
+#[cfg(target_os = "unknown")]
+#[wasm_bindgen(module = "/src/utils/http_cache.js")]
+extern "C" {
+  #[wasm_bindgen(js_name = "readCacheFile")]
+  async fn read_cache_file_js(path: &str) -> JsValue;
+
+  #[wasm_bindgen(catch, js_name = "writeCacheFile")]
+  async fn write_cache_file_js(directory: &str, path: &str, data: &str)
+    -> Result<JsValue, JsValue>;
+}
+
+#[cfg(target_os = "unknown")]
+async fn fetch_conditional(
+  location: &str,
+  etag: Option<&str>,
+  last_modified: Option<&str>,
+  maximum_size: Option<usize>,
+) -> Result<HttpCacheResponse, FetchTextError> {
+  let response = fetch_conditional_js(
+    location,
+    etag.map(str::to_owned),
+    last_modified.map(str::to_owned),
+    maximum_size.map(|maximum_size| maximum_size as f64),
+  )
+  .await?;
+
+  let get_member = |name: &str| js_sys::Reflect::get(&response, &name.into()).ok();
+  let status = get_member("status")
+    .and_then(|status| status.as_f64())
+    .ok_or(FetchTextError::HttpError)?;
+  let body = get_member("text").and_then(|text| text.as_string());
+  if let Some(body) = &body {
+    check_text_size(body.len(), maximum_size)?;
+  }
+
+  Ok(HttpCacheResponse {
+    status: status as u16,
+    etag: get_member("etag").and_then(|etag| etag.as_string()),
+    last_modified: get_member("lastModified").and_then(|last_modified| last_modified.as_string()),
+    body,
+  })
+}
+
+#[cfg(target_os = "unknown")]
+async fn read_cache_file(path: &str) -> Option<String> {
+  read_cache_file_js(path).await.as_string()
+}
+
+#[cfg(target_os = "unknown")]
+async fn write_cache_file(directory: &str, path: &str, data: &str) -> Result<(), FetchTextError> {
+  write_cache_file_js(directory, path, data)
+    .await
+    .map_err(|_error| FetchTextError::IoError)?;
+  Ok(())
+}
+
+impl Fetcher for HttpCacheFetcher {
+  fn fetch_text<'a>(
+    &'a self,
+    location: &'a str,
+    maximum_size: Option<usize>,
+  ) -> LocalBoxFuture<'a, Result<String, FetchTextError>> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+      self.fetch_http_text(location, maximum_size).boxed_local()
+    } else {
+      fetch_file_text(location, maximum_size).boxed_local()
+    }
+  }
+}
+
+#[cfg(not(target_os = "unknown"))]
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::documents::DocumentContext;
+  use crate::utils::NodeLocation;
+  use std::io::{BufRead, BufReader, Write};
+  use std::net::TcpListener;
+  use std::sync::{Arc, Mutex};
+
+  #[tokio::test]
+  async fn test_http_cache_fetcher() {
+    /*
+    a local stand-in for a http server, that serves a document with an ETag
+    and answers conditional requests with 304 Not Modified
+    */
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    {
+      let requests = requests.clone();
+      std::thread::spawn(move || {
+        for stream in listener.incoming() {
+          let Ok(mut stream) = stream else {
+            continue;
+          };
+          let mut reader = BufReader::new(stream.try_clone().unwrap());
+          let mut headers = Vec::new();
+          loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+              break;
+            }
+            headers.push(line.trim().to_lowercase());
+          }
+          let conditional = headers.iter().any(|header| {
+            header
+              .split_once(':')
+              .map(|(name, value)| name.trim() == "if-none-match" && value.trim() == "\"v1\"")
+              .unwrap_or(false)
+          });
+          requests.lock().unwrap().push(conditional);
+
+          let response = if conditional {
+            "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_owned()
+          } else {
+            let body = "openapi: 3.0.0";
+            format!(
+              "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+              body.len(),
+              body
+            )
+          };
+          let _ = stream.write_all(response.as_bytes());
+        }
+      });
+    }
+
+    let directory = std::env::temp_dir().join(format!(
+      "skiffa-http-cache-{}-{}",
+      std::process::id(),
+      address.port()
+    ));
+    let location = format!("http://{}/api.yaml", address);
+
+    let mut fetcher = HttpCacheFetcher::new(&directory);
+
+    /*
+    nothing is cached in offline mode yet
+    */
+    fetcher.set_offline(true);
+    assert!(fetcher.fetch_text(&location, None).await.is_err());
+    fetcher.set_offline(false);
+
+    let text = fetcher.fetch_text(&location, None).await.unwrap();
+    assert_eq!(text, "openapi: 3.0.0");
+
+    let text = fetcher.fetch_text(&location, None).await.unwrap();
+    assert_eq!(text, "openapi: 3.0.0");
+
+    fetcher.set_offline(true);
+    let text = fetcher.fetch_text(&location, None).await.unwrap();
+    assert_eq!(text, "openapi: 3.0.0");
+
+    /*
+    the first request is unconditional, the second one revalidates and the
+    offline request never reaches the server
+    */
+    assert_eq!(*requests.lock().unwrap(), vec![false, true]);
+
+    /*
+    the context retrieves http documents via the cache
+    */
+    let context = DocumentContext::new();
+    let mut http_cache = HttpCacheFetcher::new(&directory);
+    http_cache.set_offline(true);
+    context.set_http_cache(Some(http_cache));
+    let retrieval_location: NodeLocation = format!("{}#", location).parse().unwrap();
+    context.load_node(&retrieval_location).await.unwrap();
+    assert!(context.get_node(&retrieval_location).is_some());
+    assert_eq!(requests.lock().unwrap().len(), 2);
+
+    let _ = std::fs::remove_dir_all(&directory);
+  }
+}
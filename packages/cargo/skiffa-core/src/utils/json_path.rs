@@ -0,0 +1,1230 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A JSONPath query (RFC 9535), like `$.paths.*[?@.tags[0] == 'internal']`.
+/// The query selects nodes from a (root) node, the result is the list of
+/// selected nodes with their pointer.
+///
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+  segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+  /**
+   * selects children of the node
+   */
+  Child(Vec<Selector>),
+  /**
+   * selects children of the node and of all of its descendants
+   */
+  Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone)]
+enum Selector {
+  Name(String),
+  Wildcard,
+  Index(i64),
+  Slice {
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+  },
+  Filter(FilterExpression),
+}
+
+#[derive(Debug, Clone)]
+enum FilterExpression {
+  Or(Vec<FilterExpression>),
+  And(Vec<FilterExpression>),
+  Not(Box<FilterExpression>),
+  Comparison(Comparable, ComparisonOperator, Comparable),
+  /**
+   * true if the query selects at least one node
+   */
+  Test(FilterQuery),
+  /**
+   * a function that returns a logical value, like match
+   */
+  Function(FunctionExpression),
+}
+
+#[derive(Debug, Clone)]
+enum Comparable {
+  Literal(serde_json::Value),
+  /**
+   * a query that selects at most one node, or any query if it is the
+   * argument of a function that takes nodes
+   */
+  Query(FilterQuery),
+  /**
+   * a function that returns a value, like length
+   */
+  Function(FunctionExpression),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ComparisonOperator {
+  Equal,
+  NotEqual,
+  Less,
+  LessOrEqual,
+  Greater,
+  GreaterOrEqual,
+}
+
+/**
+the function extensions of RFC 9535
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+  Length,
+  Count,
+  Match,
+  Search,
+  Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FunctionType {
+  /**
+   * a json value, or nothing
+   */
+  Value,
+  /**
+   * a list of nodes
+   */
+  Nodes,
+  /**
+   * true or false
+   */
+  Logical,
+}
+
+#[derive(Debug, Clone)]
+struct FunctionExpression {
+  function: Function,
+  arguments: Vec<Comparable>,
+  /**
+   * the regular expression of match and search if the pattern is a literal,
+   * so it is only compiled once
+   */
+  regex: Option<regex::Regex>,
+}
+
+#[derive(Debug, Clone)]
+struct FilterQuery {
+  /**
+   * relative queries start at the current node (`@`) instead of the root
+   * node (`$`)
+   */
+  relative: bool,
+  path: JsonPath,
+}
+
+/// A node selected by a query, with the pointer of the node from the root
+/// node
+///
+pub type JsonPathNode<'a> = (Vec<String>, &'a serde_json::Value);
+
+impl JsonPath {
+  /// Selects nodes from the root node, in the order of the query.
+  ///
+  pub fn query<'a>(&self, root_node: &'a serde_json::Value) -> Vec<JsonPathNode<'a>> {
+    self.select(root_node, vec![(Vec::new(), root_node)])
+  }
+
+  fn select<'a>(
+    &self,
+    root_node: &'a serde_json::Value,
+    mut nodes: Vec<JsonPathNode<'a>>,
+  ) -> Vec<JsonPathNode<'a>> {
+    for segment in &self.segments {
+      let mut selected_nodes = Vec::new();
+      for (pointer, node) in nodes {
+        segment.select(root_node, &pointer, node, &mut selected_nodes);
+      }
+      nodes = selected_nodes;
+    }
+    nodes
+  }
+
+  /**
+  a singular query selects at most one node
+  */
+  fn is_singular(&self) -> bool {
+    self.segments.iter().all(|segment| match segment {
+      Segment::Child(selectors) => {
+        matches!(
+          selectors.as_slice(),
+          [Selector::Name(_)] | [Selector::Index(_)]
+        )
+      }
+      Segment::Descendant(_) => false,
+    })
+  }
+}
+
+impl Segment {
+  fn select<'a>(
+    &self,
+    root_node: &'a serde_json::Value,
+    pointer: &[String],
+    node: &'a serde_json::Value,
+    selected_nodes: &mut Vec<JsonPathNode<'a>>,
+  ) {
+    match self {
+      Self::Child(selectors) => {
+        for selector in selectors {
+          selector.select(root_node, pointer, node, selected_nodes);
+        }
+      }
+      Self::Descendant(selectors) => {
+        let mut descendant_nodes = Vec::new();
+        collect_descendants(pointer.to_vec(), node, &mut descendant_nodes);
+        for (descendant_pointer, descendant_node) in descendant_nodes {
+          for selector in selectors {
+            selector.select(
+              root_node,
+              &descendant_pointer,
+              descendant_node,
+              selected_nodes,
+            );
+          }
+        }
+      }
+    }
+  }
+}
+
+impl Selector {
+  fn select<'a>(
+    &self,
+    root_node: &'a serde_json::Value,
+    pointer: &[String],
+    node: &'a serde_json::Value,
+    selected_nodes: &mut Vec<JsonPathNode<'a>>,
+  ) {
+    match self {
+      Self::Name(name) => {
+        if let Some(child_node) = node.as_object().and_then(|node| node.get(name)) {
+          selected_nodes.push((push_pointer(pointer, name.clone()), child_node));
+        }
+      }
+      Self::Wildcard => selected_nodes.extend(get_children(pointer, node)),
+      Self::Index(index) => {
+        let Some(array_node) = node.as_array() else {
+          return;
+        };
+        let index = if *index < 0 {
+          array_node.len() as i64 + index
+        } else {
+          *index
+        };
+        if let Some(child_node) = usize::try_from(index)
+          .ok()
+          .and_then(|index| array_node.get(index))
+        {
+          selected_nodes.push((push_pointer(pointer, index.to_string()), child_node));
+        }
+      }
+      Self::Slice { start, end, step } => {
+        let Some(array_node) = node.as_array() else {
+          return;
+        };
+        let length = array_node.len() as i64;
+        let step = step.unwrap_or(1);
+        let normalize = |index: i64| if index >= 0 { index } else { length + index };
+
+        let mut select_index = |index: i64| {
+          if let Some(child_node) = usize::try_from(index)
+            .ok()
+            .and_then(|index| array_node.get(index))
+          {
+            selected_nodes.push((push_pointer(pointer, index.to_string()), child_node));
+          }
+        };
+        match step {
+          0 => {}
+          step if step > 0 => {
+            let lower = normalize(start.unwrap_or(0)).clamp(0, length);
+            let upper = normalize(end.unwrap_or(length)).clamp(0, length);
+            let mut index = lower;
+            while index < upper {
+              select_index(index);
+              index += step;
+            }
+          }
+          step => {
+            let upper = start
+              .map(normalize)
+              .unwrap_or(length - 1)
+              .clamp(-1, length - 1);
+            let lower = end.map(normalize).unwrap_or(-1).clamp(-1, length - 1);
+            let mut index = upper;
+            while lower < index {
+              select_index(index);
+              index += step;
+            }
+          }
+        }
+      }
+      Self::Filter(expression) => selected_nodes.extend(
+        get_children(pointer, node)
+          .into_iter()
+          .filter(|(_, child_node)| expression.test(root_node, child_node)),
+      ),
+    }
+  }
+}
+
+impl FilterExpression {
+  fn test(&self, root_node: &serde_json::Value, current_node: &serde_json::Value) -> bool {
+    match self {
+      Self::Or(expressions) => expressions
+        .iter()
+        .any(|expression| expression.test(root_node, current_node)),
+      Self::And(expressions) => expressions
+        .iter()
+        .all(|expression| expression.test(root_node, current_node)),
+      Self::Not(expression) => !expression.test(root_node, current_node),
+      Self::Comparison(left, operator, right) => {
+        let left = left.evaluate(root_node, current_node);
+        let right = right.evaluate(root_node, current_node);
+        let (left, right) = (left.as_deref(), right.as_deref());
+        match operator {
+          ComparisonOperator::Equal => is_equal(left, right),
+          ComparisonOperator::NotEqual => !is_equal(left, right),
+          ComparisonOperator::Less => is_less(left, right),
+          ComparisonOperator::LessOrEqual => is_less(left, right) || is_equal(left, right),
+          ComparisonOperator::Greater => is_less(right, left),
+          ComparisonOperator::GreaterOrEqual => is_less(right, left) || is_equal(left, right),
+        }
+      }
+      Self::Test(query) => !query.select(root_node, current_node).is_empty(),
+      Self::Function(function) => function.test(root_node, current_node),
+    }
+  }
+}
+
+impl Comparable {
+  /**
+  the value to compare, None if the query selects nothing
+  */
+  fn evaluate<'a>(
+    &'a self,
+    root_node: &'a serde_json::Value,
+    current_node: &'a serde_json::Value,
+  ) -> Option<Cow<'a, serde_json::Value>> {
+    match self {
+      Self::Literal(value) => Some(Cow::Borrowed(value)),
+      Self::Query(query) => {
+        let mut nodes = query.select(root_node, current_node);
+        match nodes.len() {
+          1 => nodes.pop().map(|(_, node)| Cow::Borrowed(node)),
+          _ => None,
+        }
+      }
+      Self::Function(function) => function.evaluate(root_node, current_node),
+    }
+  }
+
+  fn get_type(&self) -> FunctionType {
+    match self {
+      Self::Literal(_) => FunctionType::Value,
+      Self::Query(query) if query.path.is_singular() => FunctionType::Value,
+      Self::Query(_) => FunctionType::Nodes,
+      Self::Function(function) => function.function.get_result_type(),
+    }
+  }
+}
+
+impl Function {
+  fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "length" => Some(Self::Length),
+      "count" => Some(Self::Count),
+      "match" => Some(Self::Match),
+      "search" => Some(Self::Search),
+      "value" => Some(Self::Value),
+      _ => None,
+    }
+  }
+
+  fn get_parameter_types(&self) -> &'static [FunctionType] {
+    match self {
+      Self::Length => &[FunctionType::Value],
+      Self::Count | Self::Value => &[FunctionType::Nodes],
+      Self::Match | Self::Search => &[FunctionType::Value, FunctionType::Value],
+    }
+  }
+
+  fn get_result_type(&self) -> FunctionType {
+    match self {
+      Self::Length | Self::Count | Self::Value => FunctionType::Value,
+      Self::Match | Self::Search => FunctionType::Logical,
+    }
+  }
+}
+
+impl FunctionExpression {
+  /**
+  the value of a function that returns a value, None for nothing
+  */
+  fn evaluate<'a>(
+    &'a self,
+    root_node: &'a serde_json::Value,
+    current_node: &'a serde_json::Value,
+  ) -> Option<Cow<'a, serde_json::Value>> {
+    let argument = self.arguments.first()?;
+    match self.function {
+      Function::Length => {
+        let length = match argument.evaluate(root_node, current_node)?.as_ref() {
+          serde_json::Value::String(value) => value.chars().count(),
+          serde_json::Value::Array(value) => value.len(),
+          serde_json::Value::Object(value) => value.len(),
+          _ => return None,
+        };
+        Some(Cow::Owned(length.into()))
+      }
+      Function::Count => {
+        let Comparable::Query(query) = argument else {
+          return None;
+        };
+        let count = query.select(root_node, current_node).len();
+        Some(Cow::Owned(count.into()))
+      }
+      Function::Value => {
+        let Comparable::Query(query) = argument else {
+          return None;
+        };
+        let mut nodes = query.select(root_node, current_node);
+        match nodes.len() {
+          1 => nodes.pop().map(|(_, node)| Cow::Borrowed(node)),
+          _ => None,
+        }
+      }
+      Function::Match | Function::Search => None,
+    }
+  }
+
+  /**
+  the result of a function that returns a logical value
+  */
+  fn test(&self, root_node: &serde_json::Value, current_node: &serde_json::Value) -> bool {
+    let [value, pattern] = self.arguments.as_slice() else {
+      return false;
+    };
+    let Some(serde_json::Value::String(value)) =
+      value.evaluate(root_node, current_node).map(Cow::into_owned)
+    else {
+      return false;
+    };
+
+    let regex = match &self.regex {
+      Some(regex) => Cow::Borrowed(regex),
+      None => {
+        let Some(serde_json::Value::String(pattern)) = pattern
+          .evaluate(root_node, current_node)
+          .map(Cow::into_owned)
+        else {
+          return false;
+        };
+        match make_regex(&pattern, self.function == Function::Match) {
+          Some(regex) => Cow::Owned(regex),
+          None => return false,
+        }
+      }
+    };
+
+    regex.is_match(&value)
+  }
+}
+
+/**
+translate an I-Regexp (RFC 9485) pattern to a regular expression, a dot does
+not match line breaks. match has to match the whole value, search any part
+of it.
+*/
+fn make_regex(pattern: &str, full_match: bool) -> Option<regex::Regex> {
+  let mut translated_pattern = String::new();
+  let mut in_class = false;
+  let mut escaped = false;
+  for value in pattern.chars() {
+    match value {
+      _ if escaped => {
+        escaped = false;
+        translated_pattern.push(value);
+      }
+      '\\' => {
+        escaped = true;
+        translated_pattern.push(value);
+      }
+      '[' => {
+        in_class = true;
+        translated_pattern.push(value);
+      }
+      ']' => {
+        in_class = false;
+        translated_pattern.push(value);
+      }
+      '.' if !in_class => translated_pattern.push_str(r"[^\n\r]"),
+      _ => translated_pattern.push(value),
+    }
+  }
+
+  let translated_pattern = if full_match {
+    format!(r"\A(?:{})\z", translated_pattern)
+  } else {
+    translated_pattern
+  };
+  regex::Regex::new(&translated_pattern).ok()
+}
+
+impl FilterQuery {
+  fn select<'a>(
+    &self,
+    root_node: &'a serde_json::Value,
+    current_node: &'a serde_json::Value,
+  ) -> Vec<JsonPathNode<'a>> {
+    let start_node = if self.relative {
+      current_node
+    } else {
+      root_node
+    };
+    self.path.select(root_node, vec![(Vec::new(), start_node)])
+  }
+}
+
+fn push_pointer(pointer: &[String], member: String) -> Vec<String> {
+  let mut pointer = pointer.to_vec();
+  pointer.push(member);
+  pointer
+}
+
+fn get_children<'a>(pointer: &[String], node: &'a serde_json::Value) -> Vec<JsonPathNode<'a>> {
+  match node {
+    serde_json::Value::Object(object_node) => object_node
+      .iter()
+      .map(|(member, child_node)| (push_pointer(pointer, member.clone()), child_node))
+      .collect(),
+    serde_json::Value::Array(array_node) => array_node
+      .iter()
+      .enumerate()
+      .map(|(index, child_node)| (push_pointer(pointer, index.to_string()), child_node))
+      .collect(),
+    _ => Vec::new(),
+  }
+}
+
+/**
+the node and all of its descendants, parents before their children
+*/
+fn collect_descendants<'a>(
+  pointer: Vec<String>,
+  node: &'a serde_json::Value,
+  descendant_nodes: &mut Vec<JsonPathNode<'a>>,
+) {
+  let child_nodes = get_children(&pointer, node);
+  descendant_nodes.push((pointer, node));
+  for (child_pointer, child_node) in child_nodes {
+    collect_descendants(child_pointer, child_node, descendant_nodes);
+  }
+}
+
+fn is_equal(left: Option<&serde_json::Value>, right: Option<&serde_json::Value>) -> bool {
+  match (left, right) {
+    (None, None) => true,
+    (Some(left), Some(right)) => is_value_equal(left, right),
+    _ => false,
+  }
+}
+
+/**
+values are equal if they are deep equal, numbers are equal if they have the
+same value (so 1 and 1.0 are equal)
+*/
+fn is_value_equal(left: &serde_json::Value, right: &serde_json::Value) -> bool {
+  match (left, right) {
+    (serde_json::Value::Number(left), serde_json::Value::Number(right)) => {
+      match (left.as_i64(), right.as_i64()) {
+        (Some(left), Some(right)) => left == right,
+        _ => left.as_f64() == right.as_f64(),
+      }
+    }
+    (serde_json::Value::Array(left), serde_json::Value::Array(right)) => {
+      left.len() == right.len()
+        && left
+          .iter()
+          .zip(right)
+          .all(|(left, right)| is_value_equal(left, right))
+    }
+    (serde_json::Value::Object(left), serde_json::Value::Object(right)) => {
+      left.len() == right.len()
+        && left.iter().all(|(member, left)| {
+          right
+            .get(member)
+            .is_some_and(|right| is_value_equal(left, right))
+        })
+    }
+    _ => left == right,
+  }
+}
+
+/**
+only numbers and strings can be ordered
+*/
+fn is_less(left: Option<&serde_json::Value>, right: Option<&serde_json::Value>) -> bool {
+  match (left, right) {
+    (Some(serde_json::Value::Number(left)), Some(serde_json::Value::Number(right))) => {
+      match (left.as_i64(), right.as_i64()) {
+        (Some(left), Some(right)) => left < right,
+        _ => left.as_f64() < right.as_f64(),
+      }
+    }
+    (Some(serde_json::Value::String(left)), Some(serde_json::Value::String(right))) => left < right,
+    _ => false,
+  }
+}
+
+impl FromStr for JsonPath {
+  type Err = ParseJsonPathError;
+
+  fn from_str(input: &str) -> Result<Self, Self::Err> {
+    let mut parser = JsonPathParser {
+      chars: input.chars().collect(),
+      position: 0,
+    };
+    parser.expect('$')?;
+    let path = parser.parse_segments()?;
+    if parser.peek().is_some() {
+      return Err(parser.error());
+    }
+
+    Ok(path)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseJsonPathError {
+  /**
+   * the query is not valid, at the position (in characters)
+   */
+  InvalidInput(usize),
+}
+
+impl Display for ParseJsonPathError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::InvalidInput(position) => write!(f, "Invalid input at position {}", position),
+    }
+  }
+}
+
+/**
+largest integer that can be exactly represented in every json implementation
+*/
+const MAXIMUM_INTEGER: i64 = (1 << 53) - 1;
+
+struct JsonPathParser {
+  chars: Vec<char>,
+  position: usize,
+}
+
+impl JsonPathParser {
+  fn error(&self) -> ParseJsonPathError {
+    ParseJsonPathError::InvalidInput(self.position)
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.chars.get(self.position).copied()
+  }
+
+  fn next(&mut self) -> Option<char> {
+    let value = self.peek();
+    if value.is_some() {
+      self.position += 1;
+    }
+    value
+  }
+
+  fn is_next(&self, value: &str) -> bool {
+    value
+      .chars()
+      .enumerate()
+      .all(|(offset, expected)| self.chars.get(self.position + offset) == Some(&expected))
+  }
+
+  fn eat(&mut self, value: &str) -> bool {
+    if self.is_next(value) {
+      self.position += value.chars().count();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, value: char) -> Result<(), ParseJsonPathError> {
+    if self.peek() == Some(value) {
+      self.position += 1;
+      Ok(())
+    } else {
+      Err(self.error())
+    }
+  }
+
+  fn skip_blank(&mut self) {
+    while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+      self.position += 1;
+    }
+  }
+
+  fn parse_segments(&mut self) -> Result<JsonPath, ParseJsonPathError> {
+    let mut segments = Vec::new();
+
+    loop {
+      let position = self.position;
+      self.skip_blank();
+
+      if self.eat("..") {
+        let selectors = match self.peek() {
+          Some('[') => self.parse_bracketed_selection()?,
+          Some('*') => {
+            self.next();
+            vec![Selector::Wildcard]
+          }
+          _ => vec![Selector::Name(self.parse_member_name()?)],
+        };
+        segments.push(Segment::Descendant(selectors));
+      } else if self.eat(".") {
+        let selectors = match self.peek() {
+          Some('*') => {
+            self.next();
+            vec![Selector::Wildcard]
+          }
+          _ => vec![Selector::Name(self.parse_member_name()?)],
+        };
+        segments.push(Segment::Child(selectors));
+      } else if self.peek() == Some('[') {
+        segments.push(Segment::Child(self.parse_bracketed_selection()?));
+      } else {
+        /*
+        blank space is only allowed between segments
+        */
+        self.position = position;
+        break;
+      }
+    }
+
+    Ok(JsonPath { segments })
+  }
+
+  fn parse_member_name(&mut self) -> Result<String, ParseJsonPathError> {
+    let is_name_first =
+      |value: char| value.is_ascii_alphabetic() || value == '_' || !value.is_ascii();
+
+    let mut name = String::new();
+    match self.peek() {
+      Some(value) if is_name_first(value) => name.push(value),
+      _ => return Err(self.error()),
+    }
+    self.next();
+    while let Some(value) = self.peek() {
+      if !is_name_first(value) && !value.is_ascii_digit() {
+        break;
+      }
+      name.push(value);
+      self.next();
+    }
+
+    Ok(name)
+  }
+
+  fn parse_bracketed_selection(&mut self) -> Result<Vec<Selector>, ParseJsonPathError> {
+    self.expect('[')?;
+    let mut selectors = Vec::new();
+
+    loop {
+      self.skip_blank();
+      selectors.push(self.parse_selector()?);
+      self.skip_blank();
+      if self.eat(",") {
+        continue;
+      }
+      self.expect(']')?;
+      break;
+    }
+
+    Ok(selectors)
+  }
+
+  fn parse_selector(&mut self) -> Result<Selector, ParseJsonPathError> {
+    match self.peek() {
+      Some('\'' | '"') => Ok(Selector::Name(self.parse_string()?)),
+      Some('*') => {
+        self.next();
+        Ok(Selector::Wildcard)
+      }
+      Some('?') => {
+        self.next();
+        self.skip_blank();
+        Ok(Selector::Filter(self.parse_logical_or()?))
+      }
+      Some('-' | '0'..='9' | ':') => self.parse_index_or_slice(),
+      _ => Err(self.error()),
+    }
+  }
+
+  fn parse_index_or_slice(&mut self) -> Result<Selector, ParseJsonPathError> {
+    let start = self.parse_optional_integer()?;
+    self.skip_blank();
+    if !self.eat(":") {
+      return start.map(Selector::Index).ok_or_else(|| self.error());
+    }
+
+    self.skip_blank();
+    let end = self.parse_optional_integer()?;
+    self.skip_blank();
+    let step = if self.eat(":") {
+      self.skip_blank();
+      self.parse_optional_integer()?
+    } else {
+      None
+    };
+
+    Ok(Selector::Slice { start, end, step })
+  }
+
+  fn parse_optional_integer(&mut self) -> Result<Option<i64>, ParseJsonPathError> {
+    match self.peek() {
+      Some('-' | '0'..='9') => Ok(Some(self.parse_integer()?)),
+      _ => Ok(None),
+    }
+  }
+
+  fn parse_integer(&mut self) -> Result<i64, ParseJsonPathError> {
+    let position = self.position;
+    let negative = self.eat("-");
+    let mut digits = String::new();
+    while let Some(value @ '0'..='9') = self.peek() {
+      digits.push(value);
+      self.next();
+    }
+
+    /*
+    no leading zeros, and no negative zero
+    */
+    let valid = match digits.as_str() {
+      "" => false,
+      "0" => !negative,
+      digits => !digits.starts_with('0'),
+    };
+    let value = digits
+      .parse::<i64>()
+      .ok()
+      .filter(|value| valid && *value <= MAXIMUM_INTEGER)
+      .ok_or(ParseJsonPathError::InvalidInput(position))?;
+
+    Ok(if negative { -value } else { value })
+  }
+
+  fn parse_string(&mut self) -> Result<String, ParseJsonPathError> {
+    let quote = self.next().ok_or_else(|| self.error())?;
+    let mut value = String::new();
+
+    loop {
+      match self.next() {
+        None => return Err(self.error()),
+        Some(next) if next == quote => break,
+        Some('\\') => {
+          let escaped = match self.next() {
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('/') => '/',
+            Some('\\') => '\\',
+            Some(next) if next == quote => quote,
+            Some('u') => self.parse_unicode_escape()?,
+            _ => return Err(self.error()),
+          };
+          value.push(escaped);
+        }
+        Some(next) if next < '\u{20}' => return Err(self.error()),
+        Some(next) => value.push(next),
+      }
+    }
+
+    Ok(value)
+  }
+
+  /**
+  the character of a \uXXXX escape (the \u is already parsed), surrogate pairs
+  are two escapes
+  */
+  fn parse_unicode_escape(&mut self) -> Result<char, ParseJsonPathError> {
+    let high = self.parse_hex()?;
+    let code_point = match high {
+      0xd800..=0xdbff => {
+        if !self.eat("\\u") {
+          return Err(self.error());
+        }
+        let low = self.parse_hex()?;
+        if !(0xdc00..=0xdfff).contains(&low) {
+          return Err(self.error());
+        }
+        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+      }
+      0xdc00..=0xdfff => return Err(self.error()),
+      _ => high,
+    };
+
+    char::from_u32(code_point).ok_or_else(|| self.error())
+  }
+
+  fn parse_hex(&mut self) -> Result<u32, ParseJsonPathError> {
+    let mut value = 0;
+    for _ in 0..4 {
+      let digit = self
+        .next()
+        .and_then(|next| next.to_digit(16))
+        .ok_or_else(|| self.error())?;
+      value = value * 16 + digit;
+    }
+    Ok(value)
+  }
+
+  fn parse_logical_or(&mut self) -> Result<FilterExpression, ParseJsonPathError> {
+    let mut expressions = vec![self.parse_logical_and()?];
+    loop {
+      self.skip_blank();
+      if !self.eat("||") {
+        break;
+      }
+      self.skip_blank();
+      expressions.push(self.parse_logical_and()?);
+    }
+
+    Ok(match expressions.len() {
+      1 => expressions.remove(0),
+      _ => FilterExpression::Or(expressions),
+    })
+  }
+
+  fn parse_logical_and(&mut self) -> Result<FilterExpression, ParseJsonPathError> {
+    let mut expressions = vec![self.parse_basic_expression()?];
+    loop {
+      self.skip_blank();
+      if !self.eat("&&") {
+        break;
+      }
+      self.skip_blank();
+      expressions.push(self.parse_basic_expression()?);
+    }
+
+    Ok(match expressions.len() {
+      1 => expressions.remove(0),
+      _ => FilterExpression::And(expressions),
+    })
+  }
+
+  fn parse_basic_expression(&mut self) -> Result<FilterExpression, ParseJsonPathError> {
+    if self.eat("!") {
+      self.skip_blank();
+      let expression = match self.peek() {
+        Some('(') => self.parse_parenthesized_expression()?,
+        Some('a'..='z') => self.parse_logical_function()?,
+        _ => FilterExpression::Test(self.parse_filter_query()?),
+      };
+      return Ok(FilterExpression::Not(Box::new(expression)));
+    }
+
+    if self.peek() == Some('(') {
+      return self.parse_parenthesized_expression();
+    }
+
+    let position = self.position;
+    let left = self.parse_comparable()?;
+
+    self.skip_blank();
+    let Some(operator) = self.parse_comparison_operator() else {
+      /*
+      a query without a comparison tests if the query selects a node, a
+      function without a comparison has to return a logical value
+      */
+      return match left {
+        Comparable::Query(query) => Ok(FilterExpression::Test(query)),
+        Comparable::Function(function)
+          if function.function.get_result_type() == FunctionType::Logical =>
+        {
+          Ok(FilterExpression::Function(function))
+        }
+        _ => Err(ParseJsonPathError::InvalidInput(position)),
+      };
+    };
+    self.skip_blank();
+    let right = self.parse_comparable()?;
+
+    /*
+    only values can be compared
+    */
+    if left.get_type() != FunctionType::Value || right.get_type() != FunctionType::Value {
+      return Err(ParseJsonPathError::InvalidInput(position));
+    }
+
+    Ok(FilterExpression::Comparison(left, operator, right))
+  }
+
+  fn parse_parenthesized_expression(&mut self) -> Result<FilterExpression, ParseJsonPathError> {
+    self.expect('(')?;
+    self.skip_blank();
+    let expression = self.parse_logical_or()?;
+    self.skip_blank();
+    self.expect(')')?;
+
+    Ok(expression)
+  }
+
+  fn parse_comparison_operator(&mut self) -> Option<ComparisonOperator> {
+    for (value, operator) in [
+      ("==", ComparisonOperator::Equal),
+      ("!=", ComparisonOperator::NotEqual),
+      ("<=", ComparisonOperator::LessOrEqual),
+      (">=", ComparisonOperator::GreaterOrEqual),
+      ("<", ComparisonOperator::Less),
+      (">", ComparisonOperator::Greater),
+    ] {
+      if self.eat(value) {
+        return Some(operator);
+      }
+    }
+    None
+  }
+
+  fn parse_filter_query(&mut self) -> Result<FilterQuery, ParseJsonPathError> {
+    let relative = match self.next() {
+      Some('@') => true,
+      Some('$') => false,
+      _ => return Err(self.error()),
+    };
+    let path = self.parse_segments()?;
+
+    Ok(FilterQuery { relative, path })
+  }
+
+  fn parse_comparable(&mut self) -> Result<Comparable, ParseJsonPathError> {
+    match self.peek() {
+      Some('@' | '$') => Ok(Comparable::Query(self.parse_filter_query()?)),
+      Some('\'' | '"') => Ok(Comparable::Literal(self.parse_string()?.into())),
+      Some('-' | '0'..='9') => Ok(Comparable::Literal(self.parse_number()?)),
+      Some('a'..='z') => {
+        let position = self.position;
+        for (value, literal) in [
+          ("true", serde_json::Value::Bool(true)),
+          ("false", serde_json::Value::Bool(false)),
+          ("null", serde_json::Value::Null),
+        ] {
+          if self.eat(value) {
+            if !matches!(self.peek(), Some('a'..='z' | '0'..='9' | '_' | '(')) {
+              return Ok(Comparable::Literal(literal));
+            }
+            self.position = position;
+          }
+        }
+        Ok(Comparable::Function(self.parse_function_expression()?))
+      }
+      _ => Err(self.error()),
+    }
+  }
+
+  fn parse_logical_function(&mut self) -> Result<FilterExpression, ParseJsonPathError> {
+    let position = self.position;
+    let function = self.parse_function_expression()?;
+    if function.function.get_result_type() != FunctionType::Logical {
+      return Err(ParseJsonPathError::InvalidInput(position));
+    }
+
+    Ok(FilterExpression::Function(function))
+  }
+
+  /**
+  parse a function and check that the arguments have the type of the
+  parameters of the function
+  */
+  fn parse_function_expression(&mut self) -> Result<FunctionExpression, ParseJsonPathError> {
+    let position = self.position;
+    let mut name = String::new();
+    while let Some(value @ ('a'..='z' | '0'..='9' | '_')) = self.peek() {
+      name.push(value);
+      self.next();
+    }
+    let function = Function::from_name(&name).ok_or(ParseJsonPathError::InvalidInput(position))?;
+
+    self.expect('(')?;
+    self.skip_blank();
+    let mut arguments = Vec::new();
+    if self.peek() != Some(')') {
+      loop {
+        let argument_position = self.position;
+        let argument = self.parse_comparable()?;
+        let parameter_type = function.get_parameter_types().get(arguments.len());
+        let valid = match (parameter_type, argument.get_type()) {
+          (Some(FunctionType::Value), FunctionType::Value) => true,
+          (Some(FunctionType::Nodes), _) => matches!(argument, Comparable::Query(_)),
+          _ => false,
+        };
+        if !valid {
+          return Err(ParseJsonPathError::InvalidInput(argument_position));
+        }
+        arguments.push(argument);
+
+        self.skip_blank();
+        if !self.eat(",") {
+          break;
+        }
+        self.skip_blank();
+      }
+    }
+    self.expect(')')?;
+
+    if arguments.len() != function.get_parameter_types().len() {
+      return Err(ParseJsonPathError::InvalidInput(position));
+    }
+
+    let regex = match arguments.get(1) {
+      Some(Comparable::Literal(serde_json::Value::String(pattern)))
+        if matches!(function, Function::Match | Function::Search) =>
+      {
+        make_regex(pattern, function == Function::Match)
+      }
+      _ => None,
+    };
+
+    Ok(FunctionExpression {
+      function,
+      arguments,
+      regex,
+    })
+  }
+
+  fn parse_number(&mut self) -> Result<serde_json::Value, ParseJsonPathError> {
+    let position = self.position;
+    let mut number = String::new();
+    while let Some(value @ ('0'..='9' | '-' | '+' | '.' | 'e' | 'E')) = self.peek() {
+      number.push(value);
+      self.next();
+    }
+
+    /*
+    the number syntax is the same as json, except that negative zero is
+    allowed
+    */
+    serde_json::from_str::<serde_json::Number>(&number)
+      .map(serde_json::Value::Number)
+      .map_err(|_error| ParseJsonPathError::InvalidInput(position))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_json_path() {
+    let node = serde_json::json!({
+      "paths": {
+        "/a": {
+          "get": { "tags": ["internal"], "operationId": "getA" },
+          "put": { "tags": ["public"], "operationId": "putA" },
+        },
+        "/b": {
+          "get": { "operationId": "getB", "deprecated": true },
+        },
+      },
+      "numbers": [1, 2.0, 3, -4],
+    });
+
+    let query = |path: &str| {
+      let path: JsonPath = path.parse().unwrap();
+      path
+        .query(&node)
+        .into_iter()
+        .map(|(pointer, _)| pointer.join("/"))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(query("$"), vec![""]);
+    assert_eq!(query("$.paths['/a'].get"), vec!["paths//a/get"]);
+    assert_eq!(query("$.numbers[-1]"), vec!["numbers/3"]);
+    assert_eq!(query("$.numbers[0, 2]"), vec!["numbers/0", "numbers/2"]);
+    assert_eq!(
+      query("$.paths.*[?@.tags[0] == 'internal']"),
+      vec!["paths//a/get"]
+    );
+    assert_eq!(
+      query("$.paths.*[?!@.tags || @.tags[0] != 'public']"),
+      vec!["paths//a/get", "paths//b/get"]
+    );
+    assert_eq!(query("$.numbers[?@ >= 2 && @ < 3]"), vec!["numbers/1"]);
+    assert_eq!(query("$.numbers[?@ == 2]"), vec!["numbers/1"]);
+    assert_eq!(query("$..operationId").len(), 3);
+    assert_eq!(query("$..[?@.deprecated]"), vec!["paths//b/get"]);
+    assert_eq!(query("$.missing"), Vec::<String>::new());
+
+    assert_eq!(query("$.numbers[1:3]"), vec!["numbers/1", "numbers/2"]);
+    assert_eq!(query("$.numbers[::2]"), vec!["numbers/0", "numbers/2"]);
+    assert_eq!(
+      query("$.numbers[::-1]"),
+      vec!["numbers/3", "numbers/2", "numbers/1", "numbers/0"]
+    );
+    assert_eq!(query("$.numbers[-2:]"), vec!["numbers/2", "numbers/3"]);
+    assert_eq!(query("$.numbers[0:4:0]"), Vec::<String>::new());
+    assert_eq!(query("$.paths[?length(@) == 2]"), vec!["paths//a"]);
+    assert_eq!(query("$.paths[?count(@.*) == 1]"), vec!["paths//b"]);
+    assert_eq!(
+      query("$.paths.*.*[?match(@.operationId, 'get.')]"),
+      Vec::<String>::new()
+    );
+    assert_eq!(
+      query("$.paths.*[?match(@.operationId, 'get.')]"),
+      vec!["paths//a/get", "paths//b/get"]
+    );
+    assert_eq!(
+      query("$.paths.*[?search(@.operationId, 'A')]"),
+      vec!["paths//a/get", "paths//a/put"]
+    );
+    assert_eq!(
+      query("$.paths.*[?!match(@.operationId, 'get')]"),
+      vec!["paths//a/get", "paths//a/put", "paths//b/get"]
+    );
+    assert_eq!(
+      query("$.paths.*[?value(@..tags[0]) == 'public']"),
+      vec!["paths//a/put"]
+    );
+
+    for path in [
+      "",
+      "paths",
+      "$.",
+      "$[01]",
+      "$[-0]",
+      "$['a'",
+      "$[?@.* == 1]",
+      "$[?1]",
+      "$ ",
+      "$[1:2:3:4]",
+      "$[?length(@.*) == 1]",
+      "$[?count(@) == 1 == 1]",
+      "$[?match(@, 'a') == true]",
+      "$[?length(@)]",
+      "$[?unknown(@)]",
+      "$[?count(1) == 1]",
+    ] {
+      assert!(path.parse::<JsonPath>().is_err(), "{}", path);
+    }
+  }
+}
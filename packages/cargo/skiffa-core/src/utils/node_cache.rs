@@ -1,4 +1,7 @@
-use super::{fetch_text, FetchTextError, NodeLocation};
+use super::{
+  index_node_positions, DefaultFetcher, FetchTextError, Fetcher, JsonPath, NodeLocation,
+  NodePosition, NodePositionIndex,
+};
 use std::collections::{btree_map, BTreeMap};
 use std::iter;
 
@@ -9,6 +12,11 @@ use std::iter;
 #[derive(Default)]
 pub struct NodeCache {
   root_nodes: BTreeMap<NodeLocation, serde_json::Value>,
+  /**
+   * positions of the nodes in the source text, by root location. Only nodes
+   * that are loaded from text have positions.
+   */
+  root_positions: BTreeMap<NodeLocation, NodePositionIndex>,
 }
 
 impl NodeCache {
@@ -60,28 +68,137 @@ impl NodeCache {
     nodes.pop()
   }
 
+  /// Locations of the nodes that are selected by the JSONPath query. The
+  /// query runs against the node at the retrieval location, that is the root
+  /// (`$`) of the query.
+  ///
+  pub fn query(&self, retrieval_location: &NodeLocation, path: &JsonPath) -> Vec<NodeLocation> {
+    let Some(node) = self.get_node(retrieval_location) else {
+      return Vec::new();
+    };
+
+    path
+      .query(node)
+      .into_iter()
+      .map(|(pointer, _)| retrieval_location.push_pointer(pointer))
+      .collect()
+  }
+
+  /// Retrieves the node to change it, like when applying an overlay.
+  ///
+  pub fn get_node_mut(
+    &mut self,
+    retrieval_location: &NodeLocation,
+  ) -> Option<&mut serde_json::Value> {
+    let root_location = retrieval_location.set_root();
+    let pointer = retrieval_location.get_pointer().unwrap_or_default();
+    let root_node = self.root_nodes.get_mut(&root_location)?;
+
+    pointer
+      .iter()
+      .try_fold(root_node, |node, member| match node {
+        serde_json::Value::Array(array_node) => array_node.get_mut(member.parse::<usize>().ok()?),
+        serde_json::Value::Object(object_node) => object_node.get_mut(member),
+        _ => None,
+      })
+  }
+
+  /// Forgets the positions of the descendants of the node, for when the node
+  /// is changed (like by an overlay) and the positions in the source text no
+  /// longer apply. The descendants get the position of the node instead.
+  ///
+  pub fn drop_node_positions(&mut self, retrieval_location: &NodeLocation) {
+    let root_location = retrieval_location.set_root();
+    let pointer = retrieval_location.get_pointer().unwrap_or_default();
+    let Some(positions) = self.root_positions.get_mut(&root_location) else {
+      return;
+    };
+
+    positions.retain(|position_pointer, _position| {
+      !(position_pointer.len() > pointer.len() && position_pointer.starts_with(&pointer))
+    });
+  }
+
+  /// Retrieves the position of the node in the source text. If the node itself
+  /// has no position, the position of the nearest ancestor that has one is
+  /// returned.
+  ///
+  pub fn get_node_position(&self, retrieval_location: &NodeLocation) -> Option<NodePosition> {
+    let root_location = retrieval_location.set_root();
+    let positions = self.root_positions.get(&root_location)?;
+    let mut pointer = retrieval_location.get_pointer().unwrap_or_default();
+
+    loop {
+      if let Some((line, column)) = positions.get(&pointer) {
+        return Some(NodePosition {
+          document_location: root_location,
+          line: *line,
+          column: *column,
+        });
+      }
+      pointer.pop()?;
+    }
+  }
+
   /// Load nodes from a location. The retrieval location is the physical location of
   /// the node, it should be a root location
   ///
   pub async fn load_from_location(
     &mut self,
     retrieval_location: &NodeLocation,
+  ) -> Result<(), NodeCacheError> {
+    self
+      .load_from_location_with_fetcher(retrieval_location, &DefaultFetcher)
+      .await
+  }
+
+  /// Load nodes from a location, the document is retrieved via the fetcher.
+  ///
+  pub async fn load_from_location_with_fetcher(
+    &mut self,
+    retrieval_location: &NodeLocation,
+    fetcher: &dyn Fetcher,
   ) -> Result<(), NodeCacheError> {
     let root_location = retrieval_location.set_root();
 
     /*
     If the document is not in the cache
     */
-    if let btree_map::Entry::Vacant(entry) = self.root_nodes.entry(root_location) {
+    if !self.root_nodes.contains_key(&root_location) {
       /*
       retrieve the document
       */
-      let data = fetch_text(&entry.key().to_fetch_string()).await?;
-      let root_node = serde_yaml::from_str(&data)?;
+      let data = fetcher
+        .fetch_text(&root_location.to_fetch_string(), None)
+        .await?;
+      self.load_from_text(&root_location, &data)?;
+    }
+
+    Ok(())
+  }
+
+  /// Load nodes from the (json or yaml) text of a document that is retrieved
+  /// from the location. This way the document can be retrieved without
+  /// borrowing the cache, for instance when retrieving documents concurrently.
+  ///
+  pub fn load_from_text(
+    &mut self,
+    retrieval_location: &NodeLocation,
+    data: &str,
+  ) -> Result<(), NodeCacheError> {
+    let root_location = retrieval_location.set_root();
+
+    /*
+    If the document is not in the cache
+    */
+    if let btree_map::Entry::Vacant(entry) = self.root_nodes.entry(root_location) {
+      let root_node = serde_yaml::from_str(data)?;
+      let positions = index_node_positions(data);
 
       /*
       populate the cache with this document
       */
+      self.root_positions.insert(entry.key().clone(), positions);
       entry.insert(root_node);
     }
 
@@ -175,6 +292,8 @@ impl From<FetchTextError> for NodeCacheError {
     match value {
       FetchTextError::IoError => Self::FetchError,
       FetchTextError::HttpError => Self::FetchError,
+      FetchTextError::NotFound => Self::FetchError,
+      FetchTextError::TooLarge => Self::FetchError,
     }
   }
 }
@@ -104,6 +104,17 @@ impl NodeLocation {
     }
   }
 
+  /*
+  True if the other location is this location, or a descendant of it
+  */
+  pub fn contains(&self, other: &NodeLocation) -> bool {
+    self.set_root() == other.set_root()
+      && other
+        .get_pointer()
+        .unwrap_or_default()
+        .starts_with(&self.get_pointer().unwrap_or_default())
+  }
+
   /*
   Append to pointer
   */
@@ -0,0 +1,225 @@
+use super::NodeLocation;
+use std::collections::BTreeMap;
+use std::{fmt, rc};
+use wasm_bindgen::prelude::*;
+use yaml_rust2::parser::{Event, Parser};
+
+/// The position of a node in the source text of the document that contains
+/// it. Lines and columns start at 1.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePosition {
+  pub document_location: NodeLocation,
+  pub line: usize,
+  pub column: usize,
+}
+
+impl fmt::Display for NodePosition {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}:{}:{}",
+      self.document_location.to_fetch_string(),
+      self.line,
+      self.column
+    )
+  }
+}
+
+#[derive(Clone)]
+#[wasm_bindgen]
+pub struct NodePositionContainer(rc::Rc<NodePosition>);
+
+#[wasm_bindgen]
+impl NodePositionContainer {
+  #[wasm_bindgen(getter, js_name = "documentLocation")]
+  pub fn document_location(&self) -> String {
+    self.0.document_location.to_fetch_string()
+  }
+
+  #[wasm_bindgen(getter, js_name = "line")]
+  pub fn line(&self) -> usize {
+    self.0.line
+  }
+
+  #[wasm_bindgen(getter, js_name = "column")]
+  pub fn column(&self) -> usize {
+    self.0.column
+  }
+}
+
+impl From<rc::Rc<NodePosition>> for NodePositionContainer {
+  fn from(value: rc::Rc<NodePosition>) -> Self {
+    Self(value)
+  }
+}
+
+/// Line and column of every node in a (yaml or json) document, by pointer.
+/// Members of an object are positioned at their key, items of an array and the
+/// root node are positioned at the start of the node.
+///
+pub type NodePositionIndex = BTreeMap<Vec<String>, (usize, usize)>;
+
+enum Frame {
+  Mapping {
+    pointer: Vec<String>,
+    /*
+    the (character) index where the parser reports the start of the mapping,
+    None if the mapping is positioned at its key
+    */
+    start: Option<usize>,
+    /*
+    the key of the member whose value is expected next. None if a key is
+    expected, Some(None) if the key is not a scalar
+    */
+    key: Option<Option<String>>,
+  },
+  Sequence {
+    pointer: Vec<String>,
+    index: usize,
+  },
+  /*
+  a node that cannot be addressed with a pointer, like a non scalar key
+  */
+  Ignored,
+}
+
+/// Indexes the positions of the nodes in the first document of the text. If
+/// the text cannot be parsed, the positions that were found up until then are
+/// returned.
+///
+pub fn index_node_positions(data: &str) -> NodePositionIndex {
+  let mut index = NodePositionIndex::new();
+  let mut stack: Vec<Frame> = Vec::new();
+  let mut parser = Parser::new_from_str(data);
+
+  while let Ok((event, marker)) = parser.next_token() {
+    /*
+    markers have a line that starts at 1 and a column that starts at 0
+    */
+    let position = (marker.line(), marker.col() + 1);
+
+    /*
+    the pointer of the node and whether the node is positioned at its own
+    start (and not at its key)
+    */
+    let pointer = match &event {
+      Event::Scalar(..) | Event::Alias(..) | Event::SequenceStart(..) | Event::MappingStart(..) => {
+        match stack.last_mut() {
+          None => {
+            index.insert(Vec::new(), position);
+            Some((Vec::new(), true))
+          }
+          Some(Frame::Ignored) => None,
+          Some(Frame::Mapping {
+            pointer,
+            start,
+            key,
+          }) => match key.take() {
+            None => {
+              /*
+              this node is the key of a member. The parser reports the start
+              of a block mapping after the first key, so we position the
+              mapping at the first key if that comes earlier
+              */
+              if let Some(start) = start.as_mut().filter(|start| marker.index() < **start) {
+                *start = marker.index();
+                index.insert(pointer.clone(), position);
+              }
+
+              if let Event::Scalar(value, ..) = &event {
+                let pointer: Vec<_> = pointer.iter().cloned().chain([value.clone()]).collect();
+                index.insert(pointer, position);
+                *key = Some(Some(value.clone()));
+              } else {
+                *key = Some(None);
+              }
+              None
+            }
+            Some(None) => None,
+            Some(Some(key)) => Some((pointer.iter().cloned().chain([key]).collect(), false)),
+          },
+          Some(Frame::Sequence {
+            pointer,
+            index: item_index,
+          }) => {
+            let pointer: Vec<_> = pointer
+              .iter()
+              .cloned()
+              .chain([item_index.to_string()])
+              .collect();
+            *item_index += 1;
+            index.insert(pointer.clone(), position);
+            Some((pointer, true))
+          }
+        }
+      }
+      _ => None,
+    };
+
+    match event {
+      Event::MappingStart(..) => stack.push(match pointer {
+        Some((pointer, positioned)) => Frame::Mapping {
+          pointer,
+          start: positioned.then_some(marker.index()),
+          key: None,
+        },
+        None => Frame::Ignored,
+      }),
+      Event::SequenceStart(..) => stack.push(match pointer {
+        Some((pointer, _positioned)) => Frame::Sequence { pointer, index: 0 },
+        None => Frame::Ignored,
+      }),
+      Event::MappingEnd | Event::SequenceEnd => {
+        stack.pop();
+      }
+      Event::DocumentEnd | Event::StreamEnd => break,
+      _ => {}
+    }
+  }
+
+  index
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_index_node_positions() {
+    let data = r#"
+a:
+  b: 1
+  c:
+    - x
+    - { d: 2 }
+e: [3, 4]
+"#;
+    let index = index_node_positions(data);
+
+    do_test(&index, &[], (2, 1));
+    do_test(&index, &["a"], (2, 1));
+    do_test(&index, &["a", "b"], (3, 3));
+    do_test(&index, &["a", "c"], (4, 3));
+    do_test(&index, &["a", "c", "0"], (5, 7));
+    do_test(&index, &["a", "c", "1"], (6, 7));
+    do_test(&index, &["a", "c", "1", "d"], (6, 9));
+    do_test(&index, &["e"], (7, 1));
+    do_test(&index, &["e", "1"], (7, 8));
+
+    let data = r#"{
+  "a": [1, { "b": true }]
+}"#;
+    let index = index_node_positions(data);
+
+    do_test(&index, &[], (1, 1));
+    do_test(&index, &["a"], (2, 3));
+    do_test(&index, &["a", "1", "b"], (2, 14));
+
+    fn do_test(index: &NodePositionIndex, pointer: &[&str], expected: (usize, usize)) {
+      let pointer: Vec<_> = pointer.iter().map(|member| member.to_string()).collect();
+      let actual = index.get(&pointer).copied();
+      assert_eq!(actual, Some(expected));
+    }
+  }
+}
@@ -0,0 +1,98 @@
+use super::NodeLocation;
+
+/// A reference ($ref) in a node, the location of the node that has the
+/// reference and the location that is referenced.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeReference {
+  pub source_location: NodeLocation,
+  pub target_location: NodeLocation,
+}
+
+/// Finds all references in the node (at the location) and its descendants.
+/// References that cannot be parsed are skipped.
+///
+pub fn find_node_references(
+  location: &NodeLocation,
+  node: &serde_json::Value,
+) -> Vec<NodeReference> {
+  let mut references = Vec::new();
+  find_node_references_into(location, node, &mut Vec::new(), &mut references);
+  references
+}
+
+fn find_node_references_into(
+  location: &NodeLocation,
+  node: &serde_json::Value,
+  pointer: &mut Vec<String>,
+  references: &mut Vec<NodeReference>,
+) {
+  match node {
+    serde_json::Value::Object(object_node) => {
+      if let Some(reference) = object_node.get("$ref").and_then(|node| node.as_str()) {
+        if let Ok(reference_location) = reference.parse::<NodeLocation>() {
+          let source_location = location.push_pointer(pointer.clone());
+          let target_location = source_location.join(&reference_location);
+          references.push(NodeReference {
+            source_location,
+            target_location,
+          });
+        }
+      }
+      for (member, node) in object_node {
+        pointer.push(member.clone());
+        find_node_references_into(location, node, pointer, references);
+        pointer.pop();
+      }
+    }
+    serde_json::Value::Array(array_node) => {
+      for (index, node) in array_node.iter().enumerate() {
+        pointer.push(index.to_string());
+        find_node_references_into(location, node, pointer, references);
+        pointer.pop();
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Formats a cycle of references like `a -> b -> a`, the first location is
+/// repeated at the end to close the cycle.
+///
+pub fn format_reference_cycle(locations: &[NodeLocation]) -> String {
+  locations
+    .iter()
+    .chain(locations.first())
+    .map(|location| location.to_string())
+    .collect::<Vec<_>>()
+    .join(" -> ")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_find_node_references() {
+    let location: NodeLocation = "http://example.com/api.yaml#/components".parse().unwrap();
+    let node = serde_json::json!({
+      "a": { "$ref": "#/components/b" },
+      "b": { "items": [{ "$ref": "other.yaml#/c" }] },
+    });
+
+    let actual = find_node_references(&location, &node);
+    let expected = vec![
+      NodeReference {
+        source_location: "http://example.com/api.yaml#/components/a".parse().unwrap(),
+        target_location: "http://example.com/api.yaml#/components/b".parse().unwrap(),
+      },
+      NodeReference {
+        source_location: "http://example.com/api.yaml#/components/b/items/0"
+          .parse()
+          .unwrap(),
+        target_location: "http://example.com/other.yaml#/c".parse().unwrap(),
+      },
+    ];
+    assert_eq!(actual, expected);
+  }
+}
@@ -0,0 +1,58 @@
+use super::NodeLocation;
+
+/// Resolves a (possibly relative) server url template against the location of
+/// the document that defines it. Relative urls are only resolved when the
+/// document is retrieved over http(s), they are returned as is otherwise.
+///
+pub fn resolve_server_url(document_location: &NodeLocation, url: &str) -> String {
+  let document_url = document_location.to_fetch_string();
+  let document_url_lower = document_url.to_lowercase();
+  if !document_url_lower.starts_with("http://") && !document_url_lower.starts_with("https://") {
+    return url.to_owned();
+  }
+
+  let Ok(url_location) = url.parse::<NodeLocation>() else {
+    return url.to_owned();
+  };
+
+  let resolved_location = document_location.set_root().join(&url_location.set_root());
+  if resolved_location == url_location.set_root() {
+    return url.to_owned();
+  }
+
+  // the location is url encoded, but we want the variables of the template to
+  // stay intact
+  resolved_location
+    .to_fetch_string()
+    .replace("%7B", "{")
+    .replace("%7D", "}")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_resolve_server_url() {
+    do_test("http://a.b.c/d/e.yaml", "/v1", "http://a.b.c/v1");
+
+    do_test(
+      "http://a.b.c/d/e.yaml",
+      "v1/{version}",
+      "http://a.b.c/d/v1/{version}",
+    );
+
+    do_test(
+      "http://a.b.c/d/e.yaml",
+      "https://{host}.x.y/{version}",
+      "https://{host}.x.y/{version}",
+    );
+
+    do_test("../d/e.yaml", "/v1", "/v1");
+
+    fn do_test(document_location: &str, url: &str, expected: &str) {
+      let actual = resolve_server_url(&document_location.parse().unwrap(), url);
+      assert_eq!(actual, expected);
+    }
+  }
+}
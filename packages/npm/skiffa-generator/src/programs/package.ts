@@ -2,6 +2,8 @@ import * as jns42Core from "@jns42/core";
 import * as jns42Generator from "@jns42/generator";
 import * as skiffaCore from "@skiffa/core";
 import assert from "assert";
+import * as fs from "fs";
+import * as os from "os";
 import * as path from "path";
 import * as yargs from "yargs";
 import { generatePackage } from "../generators.js";
@@ -107,13 +109,34 @@ async function main(options: MainOptions) {
   const apiModel = skiffaContext.getApiModel(specificationLocation);
   assert(apiModel != null);
 
-  for (const documentSchema of skiffaContext.getSchemas()) {
-    await jns42Context.loadFromLocation(
-      documentSchema.schemaLocation,
-      documentSchema.schemaLocation,
-      documentSchema.documentLocation,
-      documentSchema.defaultSchemaId,
-    );
+  // derived schemas (like the schema of a swagger 2 parameter) are not a node
+  // of the document, they are retrieved from a file but keep their location
+  const derivedSchemaDirectoryPath = fs.mkdtempSync(
+    path.join(os.tmpdir(), "skiffa-"),
+  );
+  try {
+    let derivedSchemaIndex = 0;
+    for (const documentSchema of skiffaContext.getSchemas()) {
+      let retrievalLocation = documentSchema.schemaLocation;
+      const schemaNode = documentSchema.schemaNode;
+      if (schemaNode != null) {
+        derivedSchemaIndex++;
+        const derivedSchemaPath = path.join(
+          derivedSchemaDirectoryPath,
+          `${derivedSchemaIndex}.json`,
+        );
+        fs.writeFileSync(derivedSchemaPath, schemaNode);
+        retrievalLocation = `${derivedSchemaPath}#`;
+      }
+      await jns42Context.loadFromLocation(
+        retrievalLocation,
+        documentSchema.schemaLocation,
+        documentSchema.documentLocation,
+        documentSchema.defaultSchemaId,
+      );
+    }
+  } finally {
+    fs.rmSync(derivedSchemaDirectoryPath, { recursive: true, force: true });
   }
   const specification = jns42Generator.loadSpecification(jns42Context, {
     defaultTypeName,